@@ -0,0 +1,160 @@
+use std::time::Duration;
+
+use serde::Deserialize;
+use tokio::time::{sleep, Instant};
+
+use crate::error::{Error, Result};
+
+/// Scopes requested at login, enough to read and triage issues of private repositories.
+const SCOPES: &str = "repo read:org";
+
+const GRANT_TYPE: &str = "urn:ietf:params:oauth:grant-type:device_code";
+
+/// The codes handed out when starting the device flow.
+#[derive(Deserialize)]
+pub struct DeviceCode {
+    pub device_code: String,
+    /// Code the user enters at `verification_uri`
+    pub user_code: String,
+    pub verification_uri: String,
+    /// Seconds until the codes expire
+    pub expires_in: u64,
+    /// Seconds to wait between polls
+    pub interval: u64,
+}
+
+#[derive(Deserialize)]
+struct TokenResponse {
+    access_token: Option<String>,
+    error: Option<String>,
+    error_description: Option<String>,
+    /// The raised poll interval sent along with `slow_down`
+    interval: Option<u64>,
+}
+
+/// Start the OAuth device flow of the app `client_id` at `web_url`, e.g. `https://github.com`.
+pub async fn request_device_code(
+    client: &reqwest::Client,
+    web_url: &str,
+    client_id: &str,
+) -> Result<DeviceCode> {
+    Ok(client
+        .post(format!("{}/login/device/code", web_url))
+        .header("Accept", "application/json")
+        .form(&[("client_id", client_id), ("scope", SCOPES)])
+        .send()
+        .await?
+        .error_for_status()?
+        .json::<DeviceCode>()
+        .await?)
+}
+
+/// Poll until the user entered the code of `device_code` and return the access token.
+pub async fn poll_access_token(
+    client: &reqwest::Client,
+    web_url: &str,
+    client_id: &str,
+    device_code: &DeviceCode,
+) -> Result<String> {
+    let deadline = Instant::now() + Duration::from_secs(device_code.expires_in);
+    let mut interval = device_code.interval;
+
+    loop {
+        sleep(Duration::from_secs(interval)).await;
+
+        if Instant::now() > deadline {
+            return Err(Error::Auth(String::from(
+                "The code expired, please log in again",
+            )));
+        }
+
+        let response = client
+            .post(format!("{}/login/oauth/access_token", web_url))
+            .header("Accept", "application/json")
+            .form(&[
+                ("client_id", client_id),
+                ("device_code", device_code.device_code.as_str()),
+                ("grant_type", GRANT_TYPE),
+            ])
+            .send()
+            .await?
+            .error_for_status()?
+            .json::<TokenResponse>()
+            .await?;
+
+        if let Some(token) = response.access_token {
+            return Ok(token);
+        }
+
+        match response.error.as_deref() {
+            Some("authorization_pending") => {}
+            Some("slow_down") => interval = response.interval.unwrap_or(interval + 5),
+            Some(error) => {
+                return Err(Error::Auth(
+                    response.error_description.unwrap_or(error.to_string()),
+                ))
+            }
+            None => return Err(Error::Auth(String::from("No access token in the response"))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::mock_server::{serve, MockResponse};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn polls_until_the_code_was_entered() {
+        let url = serve(vec![
+            MockResponse::json(
+                "/login/device/code",
+                r#"{"device_code":"device","user_code":"ABCD-1234","verification_uri":"https://github.com/login/device","expires_in":900,"interval":0}"#,
+            ),
+            MockResponse::json(
+                "/login/oauth/access_token",
+                r#"{"error":"authorization_pending"}"#,
+            ),
+            MockResponse::json(
+                "/login/oauth/access_token",
+                r#"{"error":"slow_down","interval":0}"#,
+            ),
+            MockResponse::json(
+                "/login/oauth/access_token",
+                r#"{"access_token":"gho_token","token_type":"bearer","scope":"repo"}"#,
+            ),
+        ])
+        .await;
+        let client = reqwest::Client::new();
+
+        let device_code = request_device_code(&client, &url, "client").await.unwrap();
+        assert_eq!(device_code.user_code, "ABCD-1234");
+
+        let token = poll_access_token(&client, &url, "client", &device_code)
+            .await
+            .unwrap();
+        assert_eq!(token, "gho_token");
+    }
+
+    #[tokio::test]
+    async fn fails_when_access_is_denied() {
+        let url = serve(vec![MockResponse::json(
+            "/login/oauth/access_token",
+            r#"{"error":"access_denied","error_description":"The authorization request was denied."}"#,
+        )])
+        .await;
+        let device_code = DeviceCode {
+            device_code: String::from("device"),
+            user_code: String::from("ABCD-1234"),
+            verification_uri: String::from("https://github.com/login/device"),
+            expires_in: 900,
+            interval: 0,
+        };
+
+        let err = poll_access_token(&reqwest::Client::new(), &url, "client", &device_code)
+            .await
+            .unwrap_err();
+        assert_eq!(err.to_string(), "The authorization request was denied.");
+    }
+}
@@ -1,41 +1,1595 @@
-use anyhow::Result;
-use crossterm::event::{self, Event, KeyCode};
-use termimad::crossterm::style::Stylize;
+use std::{
+    collections::{hash_map::Entry, HashMap},
+    sync::Arc,
+    time::Duration,
+};
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use tokio::sync::mpsc::{self, UnboundedSender};
 use tui::{backend::Backend, Terminal};
 
-use crate::{reset_terminal, ui::ui, AppState, MenuItems};
+use crate::{
+    diff::diff_lines,
+    editor::{new_issue_draft_name, Draft},
+    error::{Error, Result},
+    events::{
+        spawn_create_comment, spawn_create_issue, spawn_edit_issue, spawn_fetch_assignees,
+        spawn_fetch_comments, spawn_fetch_issue, spawn_fetch_issues, spawn_fetch_labels,
+        spawn_fetch_milestones, spawn_fetch_pull_requests, spawn_input_reader, spawn_interval,
+        spawn_load_next_profile, spawn_search_issues, spawn_set_issue_labels, spawn_set_issue_lock,
+        spawn_update_issue, AppEvent, InputPause, Task,
+    },
+    fill_user_name,
+    http::GithubClient,
+    models::{
+        config::Config,
+        confirmation::{Confirmation, IssueAction, LOCK_REASONS},
+        conflict::Conflict,
+        detail_view::DetailView,
+        filters::Filters,
+        focus::Focus,
+        issue_edit::IssueEdit,
+        label::Label,
+        milestone::Milestone,
+        new_issue::{NewIssue, NEW_ISSUE_TEMPLATE},
+        picker::{IssuePicker, Picker},
+        prompt::{Prompt, PromptKind},
+        saved_view::SavedView,
+        search_query::SearchQuery,
+        searchable::Searchable,
+        user::User,
+    },
+    reset_terminal, restore_terminal, token,
+    ui::ui,
+    AppState, Issue, MenuItems, SEARCH_PAGE_SIZE,
+};
+
+/// How often the status bar spinner advances.
+const TICK_RATE: Duration = Duration::from_millis(120);
+
+/// How close the selection gets to the end of the search results before the next page loads.
+const LOAD_MORE_MARGIN: usize = 10;
+
+/// Key presses that are handled outside of `handle_key`.
+enum Action {
+    Quit,
+    CreateIssue {
+        repository: String,
+    },
+    /// Edit the title and body of the issue at the API url `issue_url`
+    EditIssue {
+        issue_url: String,
+    },
+    /// Reply to the issue whose comments live at `comments_url`
+    CreateComment {
+        comments_url: String,
+        draft_name: String,
+    },
+    /// Load the next profile of the config file and refetch everything with it
+    SwitchProfile,
+}
+
+/// What the key handlers need to start network tasks.
+pub struct Context {
+    pub client: GithubClient,
+    pub config: Arc<Config>,
+    pub sender: UnboundedSender<AppEvent>,
+}
+
+pub async fn run_app<B: Backend>(
+    terminal: &mut Terminal<B>,
+    mut app_state: AppState,
+    client: GithubClient,
+    config: Arc<Config>,
+) -> Result<()> {
+    // Input and timers arrive on their own channel, so that switching profiles can drop the
+    // results still in flight for the previous one by replacing the network channel
+    let (ui_sender, mut ui_receiver) = mpsc::unbounded_channel();
+    let (sender, mut receiver) = mpsc::unbounded_channel();
+
+    let input_pause = spawn_input_reader(ui_sender.clone());
+    spawn_interval(ui_sender.clone(), TICK_RATE, || AppEvent::Tick);
+    spawn_interval(
+        ui_sender,
+        Duration::from_secs(config.refresh_interval.max(1)),
+        || AppEvent::Refresh,
+    );
+
+    let mut context = Context {
+        client,
+        config,
+        sender,
+    };
+    refresh(&mut app_state, &context);
 
-pub fn run_app<B: Backend>(terminal: &mut Terminal<B>, mut app_state: AppState) -> Result<()> {
     loop {
-        terminal.draw(|f| ui(f, &mut app_state))?;
+        app_state.rate_limit = context.client.rate_limit();
+        app_state.close_stale_detail_view();
+        terminal.draw(|f| ui(f, &mut app_state, &context.config))?;
+
+        let event = tokio::select! {
+            event = ui_receiver.recv() => match event {
+                Some(event) => event,
+                None => return Ok(()),
+            },
+            Some(event) = receiver.recv() => event,
+        };
+
+        match event {
+            AppEvent::Key(key) => {
+                match handle_key(&mut app_state, key, &context) {
+                    Some(Action::Quit) => return Ok(()),
+                    Some(Action::CreateIssue { repository }) => create_issue_in_editor(
+                        terminal,
+                        &input_pause,
+                        &mut app_state,
+                        &context,
+                        repository,
+                    )?,
+                    Some(Action::EditIssue { issue_url }) => edit_issue_in_editor(
+                        terminal,
+                        &input_pause,
+                        &mut app_state,
+                        &context,
+                        issue_url,
+                    )?,
+                    Some(Action::CreateComment {
+                        comments_url,
+                        draft_name,
+                    }) => create_comment_in_editor(
+                        terminal,
+                        &input_pause,
+                        &mut app_state,
+                        &context,
+                        comments_url,
+                        draft_name,
+                    )?,
+                    Some(Action::SwitchProfile) => {
+                        if let Entry::Vacant(entry) = app_state.tasks.entry(Task::Profile) {
+                            entry.insert(String::from("Loading the next profile.."));
+                            spawn_load_next_profile(
+                                context.client.clone(),
+                                context.config.clone(),
+                                context.sender.clone(),
+                            );
+                        }
+                    }
+                    None => {}
+                }
+
+                load_more_search_results(&mut app_state, &context);
+            }
+            AppEvent::Resize => {}
+            AppEvent::Tick => {
+                app_state.spinner_frame = app_state.spinner_frame.wrapping_add(1);
+            }
+            AppEvent::Refresh => refresh(&mut app_state, &context),
+            AppEvent::Progress(task, message) => {
+                // A task dropped in the meantime is not brought back
+                if let Some(progress) = app_state.tasks.get_mut(&task) {
+                    *progress = message;
+                }
+            }
+            AppEvent::Issues { generation, result } => {
+                // Issues fetched before switching views or starting a search are dropped, the
+                // task of the list shown now is left running
+                if generation == app_state.issues_generation {
+                    app_state.tasks.remove(&Task::Issues);
+
+                    match result {
+                        Ok(fetched) => {
+                            app_state
+                                .issues
+                                .replace_items(fetched.items, |issue| issue.html_url.clone());
+                            app_state.skipped_issues = fetched.skipped;
+                            app_state.error = None;
+                        }
+                        Err(err) => app_state.error = Some(err.to_string()),
+                    }
+                }
+            }
+            AppEvent::Search {
+                query,
+                append,
+                result,
+            } => {
+                // Results of a search that was replaced in the meantime are dropped, the task of
+                // the search running now is left in place
+                if let Some(search) = app_state
+                    .search_query
+                    .as_mut()
+                    .filter(|search| search.query == query)
+                {
+                    app_state.tasks.remove(&Task::Search);
+
+                    match result {
+                        Ok(page) => {
+                            search.total_count = page.total_count;
+                            search.incomplete_results = page.incomplete_results;
+                            search.next_url = page.next_url;
+
+                            if append {
+                                app_state.issues.extend(page.fetched.items);
+                                app_state.skipped_issues.extend(page.fetched.skipped);
+                            } else {
+                                app_state.issues.replace_items(page.fetched.items, |issue| {
+                                    issue.html_url.clone()
+                                });
+                                app_state.skipped_issues = page.fetched.skipped;
+                            }
+                            app_state.error = None;
+                        }
+                        Err(err) => app_state.error = Some(err.to_string()),
+                    }
+                }
+            }
+            AppEvent::ProfileLoaded(result) => {
+                app_state.tasks.remove(&Task::Profile);
+
+                match result {
+                    Ok(config) => {
+                        let (sender, new_receiver) = mpsc::unbounded_channel();
+                        receiver = new_receiver;
+                        context = Context {
+                            client: context.client.clone(),
+                            config: Arc::new(config),
+                            sender,
+                        };
+
+                        app_state = AppState {
+                            current_menu: app_state.current_menu,
+                            views: context.config.views.clone(),
+                            ..AppState::default()
+                        };
+                        refresh(&mut app_state, &context);
+                    }
+                    Err(err) => app_state.error = Some(err.to_string()),
+                }
+            }
+            AppEvent::PullRequests(result) => {
+                app_state.tasks.remove(&Task::PullRequests);
+
+                match result {
+                    Ok(pull_requests) => app_state
+                        .pull_requests
+                        .replace_items(pull_requests, |pull_request| pull_request.html_url.clone()),
+                    Err(err) => app_state.error = Some(err.to_string()),
+                }
+            }
+            AppEvent::IssueCreated(result, draft) => {
+                app_state.tasks.remove(&Task::CreateIssue);
+
+                match result {
+                    Ok(issue) => {
+                        draft.discard();
+                        app_state.current_menu = MenuItems::Issues;
+                        app_state.issues.insert_and_select(0, *issue);
+                    }
+                    Err(err) => {
+                        app_state.error = Some(format!(
+                            "{} (the draft is kept at {})",
+                            err,
+                            draft.path.display()
+                        ))
+                    }
+                }
+            }
+            AppEvent::IssueUpdated { issue_url, result } => {
+                app_state.tasks.remove(&Task::UpdateIssue(issue_url));
+
+                match result {
+                    Ok(issue) => app_state.update_issue(*issue),
+                    Err(err) => app_state.error = Some(err.to_string()),
+                }
+            }
+            AppEvent::IssueEdited {
+                issue_url,
+                result,
+                draft,
+            } => {
+                app_state.tasks.remove(&Task::UpdateIssue(issue_url));
+
+                match result {
+                    Ok(issue) => {
+                        draft.discard();
+                        app_state.update_issue(*issue);
+                    }
+                    Err(err) => {
+                        app_state.error = Some(format!(
+                            "{} (the draft is kept at {})",
+                            err,
+                            draft.path.display()
+                        ))
+                    }
+                }
+            }
+            AppEvent::EditConflict {
+                remote,
+                edit,
+                draft,
+            } => {
+                app_state
+                    .tasks
+                    .remove(&Task::UpdateIssue(remote.url.clone()));
+
+                app_state.conflict = Some(Conflict {
+                    issue_url: remote.url.clone(),
+                    issue_name: format!("#{} {}", remote.number, remote.title),
+                    edit,
+                    diff: diff_lines(
+                        &IssueEdit::template(&remote),
+                        &draft.read().unwrap_or_default(),
+                    ),
+                    draft,
+                    updated_at: remote.updated_at,
+                    scroll: 0,
+                });
+                app_state.update_issue(*remote);
+            }
+            AppEvent::IssueLockChanged {
+                issue_url,
+                locked,
+                reason,
+                result,
+            } => {
+                app_state
+                    .tasks
+                    .remove(&Task::UpdateIssue(issue_url.clone()));
+
+                match result {
+                    Ok(()) => {
+                        if let Some(issue) = app_state
+                            .issues
+                            .items
+                            .iter_mut()
+                            .find(|issue| issue.url == issue_url)
+                        {
+                            issue.locked = locked;
+                            issue.active_lock_reason = reason;
+                        }
+                        refetch_issue(&mut app_state, &context, issue_url);
+                    }
+                    Err(err) => app_state.error = Some(err.to_string()),
+                }
+            }
+            AppEvent::Labels { repository, result } => {
+                app_state.tasks.remove(&Task::Labels);
+
+                let picker = match app_state.picker.as_mut() {
+                    Some(IssuePicker::Labels(picker)) => Some(picker),
+                    _ => None,
+                };
+                // Another picker may have opened while these choices were fetched
+                let waiting = picker
+                    .as_ref()
+                    .is_some_and(|picker| picker.repository == repository);
+                if let Err(err) = choices_fetched(picker, &mut app_state.labels, repository, result)
+                {
+                    if waiting {
+                        app_state.picker = None;
+                    }
+                    app_state.error = Some(err.to_string());
+                }
+            }
+            AppEvent::Assignees { repository, result } => {
+                app_state.tasks.remove(&Task::Assignees);
+
+                let picker = match app_state.picker.as_mut() {
+                    Some(IssuePicker::Assignees(picker)) => Some(picker),
+                    _ => None,
+                };
+                let waiting = picker
+                    .as_ref()
+                    .is_some_and(|picker| picker.repository == repository);
+                if let Err(err) =
+                    choices_fetched(picker, &mut app_state.assignees, repository, result)
+                {
+                    if waiting {
+                        app_state.picker = None;
+                    }
+                    app_state.error = Some(err.to_string());
+                }
+            }
+            AppEvent::Milestones { repository, result } => {
+                app_state.tasks.remove(&Task::Milestones);
+
+                let picker = match app_state.picker.as_mut() {
+                    Some(IssuePicker::Milestone(picker)) => Some(picker),
+                    _ => None,
+                };
+                let waiting = picker
+                    .as_ref()
+                    .is_some_and(|picker| picker.repository == repository);
+                if let Err(err) =
+                    choices_fetched(picker, &mut app_state.milestones, repository, result)
+                {
+                    if waiting {
+                        app_state.picker = None;
+                    }
+                    app_state.error = Some(err.to_string());
+                }
+            }
+            AppEvent::IssueLabelsSet { issue_url, result } => {
+                app_state
+                    .tasks
+                    .remove(&Task::UpdateIssue(issue_url.clone()));
+
+                match result {
+                    Ok(labels) => {
+                        if let Some(issue) = app_state
+                            .issues
+                            .items
+                            .iter_mut()
+                            .find(|issue| issue.url == issue_url)
+                        {
+                            issue.labels = labels;
+                        }
+                        app_state.issues.apply_query();
+                        refetch_issue(&mut app_state, &context, issue_url);
+                    }
+                    Err(err) => app_state.error = Some(err.to_string()),
+                }
+            }
+            AppEvent::CommentCreated {
+                comments_url,
+                result,
+                draft,
+            } => {
+                app_state.tasks.remove(&Task::CreateComment);
+
+                match result {
+                    Ok(comment) => {
+                        draft.discard();
+
+                        if let Some(issue) = app_state
+                            .issues
+                            .items
+                            .iter_mut()
+                            .find(|issue| issue.comments_url == comments_url)
+                        {
+                            issue.comments += 1;
+
+                            let issue_url = issue.url.clone();
+                            refetch_issue(&mut app_state, &context, issue_url);
+                        }
+
+                        if let Some(detail_view) = app_state
+                            .detail_view
+                            .as_mut()
+                            .filter(|detail_view| detail_view.comments_url == comments_url)
+                        {
+                            detail_view.comments.push(comment);
+                        }
+                    }
+                    Err(err) => {
+                        app_state.error = Some(format!(
+                            "{} (the draft is kept at {})",
+                            err,
+                            draft.path.display()
+                        ))
+                    }
+                }
+            }
+            AppEvent::Comments { issue_url, result } => {
+                app_state.tasks.remove(&Task::Comments(issue_url.clone()));
+
+                // Drop the thread if the detail view was closed or moved on in the meantime
+                if let Some(detail_view) = app_state
+                    .detail_view
+                    .as_mut()
+                    .filter(|detail_view| detail_view.issue_url == issue_url)
+                {
+                    detail_view.loading = false;
+
+                    match result {
+                        Ok(comments) => detail_view.comments = comments,
+                        Err(err) => app_state.error = Some(err.to_string()),
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Load the profile after the current one in the config file, with its token resolved like
+/// on startup. The repository the lists are scoped to is kept if the profile is on the same host.
+pub async fn load_next_profile(client: &GithubClient, current: &Config) -> Result<Config> {
+    let names = Config::profile_names()?;
+    let position = names
+        .iter()
+        .position(|name| *name == current.profile)
+        .map_or(0, |position| position + 1);
+    let Some(name) = names
+        .iter()
+        .cycle()
+        .skip(position)
+        .take(names.len())
+        .find(|name| **name != current.profile)
+    else {
+        return Err(Error::Config(String::from(
+            "There is no other profile in the config file",
+        )));
+    };
+
+    let mut config = Config::load_profile(name)?;
+    // Asking git's credential helpers runs a subprocess, which must not block the event loop
+    let host = config.web_host();
+    let stored_token = config.github_access_token.clone();
+    config.github_access_token =
+        tokio::task::spawn_blocking(move || token::resolve_token(None, &host, &stored_token))
+            .await
+            .ok()
+            .flatten()
+            .map(|(token, _)| token)
+            .ok_or(Error::Auth(format!(
+                "No Github access token found for the profile {}",
+                name
+            )))?;
+    fill_user_name(client, &mut config).await?;
+
+    if config.user_name.is_empty() {
+        return Err(Error::Config(format!(
+            "No Github user name is set for the profile {}",
+            name
+        )));
+    }
+
+    // The repository belongs to the host of the previous profile
+    if config.web_host() == current.web_host() {
+        config.repository = current.repository.clone();
+    }
+
+    Ok(config)
+}
+
+/// Refetch the issue at `issue_url` after an action that answers without it, so that its
+/// `updated_at` stays current for the conflict check of the next edit.
+fn refetch_issue(app_state: &mut AppState, context: &Context, issue_url: String) {
+    app_state.tasks.insert(
+        Task::UpdateIssue(issue_url.clone()),
+        String::from("Refreshing the issue.."),
+    );
+    spawn_fetch_issue(
+        context.client.clone(),
+        context.config.clone(),
+        context.sender.clone(),
+        issue_url,
+    );
+}
+
+/// Refetch the issues and pull requests in the background, unless already in flight.
+fn refresh(app_state: &mut AppState, context: &Context) {
+    if let Some(search) = &app_state.search_query {
+        if let Entry::Vacant(entry) = app_state.tasks.entry(Task::Search) {
+            entry.insert(format!("Searching {}..", search.query));
+            spawn_search_issues(
+                context.client.clone(),
+                context.config.clone(),
+                context.sender.clone(),
+                search,
+                None,
+                // Reload every page shown so far, so the list and selection stay put
+                app_state
+                    .issues
+                    .items
+                    .len()
+                    .div_ceil(SEARCH_PAGE_SIZE)
+                    .max(1),
+            );
+        }
+    } else {
+        let filters = app_state.active_filters(&context.config).clone();
+
+        if let Entry::Vacant(entry) = app_state.tasks.entry(Task::Issues) {
+            entry.insert(String::from("Fetching issues.."));
+            spawn_fetch_issues(
+                context.client.clone(),
+                context.config.clone(),
+                context.sender.clone(),
+                app_state.issues_generation,
+                filters,
+            );
+        }
+    }
+
+    if let Entry::Vacant(entry) = app_state.tasks.entry(Task::PullRequests) {
+        entry.insert(String::from("Fetching pull requests.."));
+        spawn_fetch_pull_requests(
+            context.client.clone(),
+            context.config.clone(),
+            context.sender.clone(),
+        );
+    }
+}
+
+/// Empty the issue list before it is filled from another source, dropping the fetches in
+/// flight for the previous one.
+fn clear_issue_list(app_state: &mut AppState) {
+    app_state.current_menu = MenuItems::Issues;
+    app_state
+        .issues
+        .replace_items(Vec::new(), |issue| issue.html_url.clone());
+    app_state.skipped_issues.clear();
+    app_state.issues_generation += 1;
+    app_state.tasks.remove(&Task::Issues);
+    app_state.tasks.remove(&Task::Search);
+}
+
+/// Replace the issue list with the results of the Github search `query`, or go back to the
+/// user's issues if it is empty.
+fn start_search(app_state: &mut AppState, context: &Context, query: String) {
+    clear_issue_list(app_state);
+    app_state.current_view = None;
+    app_state.search_query = (!query.is_empty()).then(|| SearchQuery::new(query));
+
+    refresh(app_state, context);
+}
+
+/// Show the saved view at `index` in the issue list, or the user's issues if `None`.
+fn switch_view(app_state: &mut AppState, context: &Context, index: Option<usize>) {
+    clear_issue_list(app_state);
+    app_state.current_view = index;
+    app_state.search_query = index
+        .and_then(|index| app_state.views.get(index))
+        .and_then(|view| {
+            view.query.clone().map(|query| {
+                SearchQuery::new(query).sorted(view.filters.sort, view.filters.direction)
+            })
+        });
+
+    refresh(app_state, context);
+}
+
+/// Save the current filters or search query as the view `name`, replacing a view of the same
+/// name, and switch to it.
+fn save_view(app_state: &mut AppState, context: &Context, name: String) {
+    if name.is_empty() {
+        return;
+    }
 
-        if let Event::Key(key) = event::read()? {
-            match key.code {
-                // Menu switcher
-                KeyCode::Char('I') => app_state.current_menu = MenuItems::Issues,
-                KeyCode::Char('P') => app_state.current_menu = MenuItems::PullRequests,
+    let view = match &app_state.search_query {
+        Some(search) => SavedView {
+            name,
+            query: Some(search.query.clone()),
+            filters: Filters {
+                sort: search.sort,
+                direction: search.direction,
+                ..Filters::default()
+            },
+        },
+        None => SavedView {
+            name,
+            query: None,
+            filters: app_state.active_filters(&context.config).clone(),
+        },
+    };
 
-                // Issue controls
-                KeyCode::Up | KeyCode::Char('k') => app_state.issues.previous(),
-                KeyCode::Down | KeyCode::Char('j') => app_state.issues.next(),
-                KeyCode::Enter => {
-                    if let Some(index) = app_state.issues.selected() {
-                        if let Some(issue) = app_state.issues.items.get(index) {
-                            webbrowser::open(issue.html_url.as_str()).unwrap_or_else(|err| {
-                                eprintln!("{}: {}", "Error".red().bold(), err);
-                                reset_terminal()
-                                    .unwrap_or_else(|_| panic!("Failed to reset terminal"));
-                                std::process::exit(1);
-                            });
+    if let Err(err) = context.config.save_view(view.clone()) {
+        app_state.error = Some(err.to_string());
+        return;
+    }
+
+    match app_state
+        .views
+        .iter()
+        .position(|existing| existing.name == view.name)
+    {
+        Some(index) => {
+            app_state.views[index] = view;
+            app_state.current_view = Some(index);
+        }
+        None => {
+            app_state.views.push(view);
+            app_state.current_view = Some(app_state.views.len() - 1);
+        }
+    }
+}
+
+/// Fetch the next page of search results once the selection is near the end of the list.
+fn load_more_search_results(app_state: &mut AppState, context: &Context) {
+    let Some(search) = &app_state.search_query else {
+        return;
+    };
+    let Some(next_url) = &search.next_url else {
+        return;
+    };
+
+    let selected = app_state.issues.state.selected().unwrap_or(0);
+    if app_state.tasks.contains_key(&Task::Search)
+        || selected + LOAD_MORE_MARGIN < app_state.issues.visible.len()
+    {
+        return;
+    }
+
+    app_state.tasks.insert(
+        Task::Search,
+        format!(
+            "Loading more results.. {} of {}",
+            app_state.issues.items.len(),
+            search.total_count
+        ),
+    );
+    spawn_search_issues(
+        context.client.clone(),
+        context.config.clone(),
+        context.sender.clone(),
+        search,
+        Some(next_url.clone()),
+        1,
+    );
+}
+
+/// Handle a key press, returning the actions that need the terminal or the event loop.
+fn handle_key(app_state: &mut AppState, key: KeyEvent, context: &Context) -> Option<Action> {
+    // Prompt controls
+    if let Some(prompt) = app_state.prompt.as_mut() {
+        match key.code {
+            KeyCode::Char(char) => prompt.input.push(char),
+            KeyCode::Backspace => {
+                prompt.input.pop();
+            }
+            KeyCode::Esc => app_state.prompt = None,
+            KeyCode::Enter => {
+                if let Some(prompt) = app_state.prompt.take() {
+                    let input = prompt.input.trim().to_string();
+
+                    return match prompt.kind {
+                        PromptKind::CreateIssue => Some(Action::CreateIssue { repository: input }),
+                        PromptKind::SearchQuery => {
+                            start_search(app_state, context, input);
+                            None
+                        }
+                        PromptKind::SaveView => {
+                            save_view(app_state, context, input);
+                            None
+                        }
+                    };
+                }
+            }
+            _ => {}
+        }
+
+        return None;
+    }
+
+    // Confirmation dialog controls
+    if let Some(confirmation) = app_state.confirmation.as_mut() {
+        match key.code {
+            KeyCode::Left | KeyCode::BackTab => confirmation.previous_lock_reason(),
+            KeyCode::Right | KeyCode::Tab => confirmation.next_lock_reason(),
+            KeyCode::Char('y') | KeyCode::Enter => {
+                if let Some(confirmation) = app_state.confirmation.take() {
+                    apply_issue_action(app_state, context, confirmation);
+                }
+            }
+            KeyCode::Char('n') | KeyCode::Esc => app_state.confirmation = None,
+            _ => {}
+        }
+
+        return None;
+    }
+
+    // Edit conflict controls
+    if let Some(conflict) = app_state.conflict.as_mut() {
+        match key.code {
+            KeyCode::Up | KeyCode::Char('k') => conflict.scroll = conflict.scroll.saturating_sub(1),
+            KeyCode::Down | KeyCode::Char('j') => {
+                conflict.scroll = conflict.scroll.saturating_add(1)
+            }
+            KeyCode::Char('o') => {
+                if let Some(conflict) = app_state.conflict.take() {
+                    app_state.tasks.insert(
+                        Task::UpdateIssue(conflict.issue_url.clone()),
+                        format!("Saving {}..", conflict.issue_name),
+                    );
+                    spawn_edit_issue(
+                        context.client.clone(),
+                        context.config.clone(),
+                        context.sender.clone(),
+                        conflict.issue_url,
+                        conflict.edit,
+                        None,
+                        conflict.draft,
+                    );
+                }
+            }
+            KeyCode::Char('e') => {
+                // The draft stays on disk and is reopened with the edit, now that the changes
+                // on the server were seen it is based on them
+                if let Some(conflict) = app_state.conflict.take() {
+                    match conflict.draft.set_base(&conflict.updated_at.to_rfc3339()) {
+                        Ok(()) => {
+                            return Some(Action::EditIssue {
+                                issue_url: conflict.issue_url,
+                            })
                         }
+                        Err(err) => app_state.error = Some(err.to_string()),
+                    }
+                }
+            }
+            KeyCode::Char('a') | KeyCode::Esc => {
+                if let Some(conflict) = app_state.conflict.take() {
+                    conflict.draft.discard();
+                }
+            }
+            _ => {}
+        }
+
+        return None;
+    }
+
+    // The error toast is dismissed before Esc goes on to close views or clear filters
+    if app_state.error.is_some() && key.code == KeyCode::Esc {
+        app_state.error = None;
+        return None;
+    }
+
+    // Picker controls
+    if let Some(picker) = app_state.picker.as_mut() {
+        let confirmed = match picker {
+            IssuePicker::Labels(picker) => picker_key(picker, key),
+            IssuePicker::Assignees(picker) => picker_key(picker, key),
+            IssuePicker::Milestone(picker) => picker_key(picker, key),
+        };
+
+        if key.code == KeyCode::Esc {
+            app_state.picker = None;
+        } else if confirmed {
+            if let Some(picker) = app_state.picker.take() {
+                apply_picker(app_state, context, picker);
+            }
+        }
+
+        return None;
+    }
+
+    // Detail view controls
+    if let Some(detail_view) = app_state.detail_view.as_mut() {
+        match key.code {
+            KeyCode::Up | KeyCode::Char('k') => detail_view.scroll_up(),
+            KeyCode::Down | KeyCode::Char('j') => detail_view.scroll_down(),
+            KeyCode::Esc | KeyCode::Backspace => app_state.detail_view = None,
+            KeyCode::Char('m') => return comment_action(app_state),
+            KeyCode::Char('e') => return edit_action(app_state),
+            KeyCode::Char('o') => {
+                if let Some(issue) = app_state.detail_issue() {
+                    if let Err(err) = open_in_browser(&issue.html_url) {
+                        app_state.error = Some(err.to_string());
                     }
                 }
+            }
+            KeyCode::Char('q') => return Some(Action::Quit),
+            _ => {}
+        }
 
-                // Exit keys
-                KeyCode::Char('q') => return Ok(()),
+        return None;
+    }
 
-                _ => {}
+    // Search prompt controls
+    if app_state.searching {
+        let mut query = match app_state.current_menu {
+            MenuItems::Issues => app_state.issues.query.clone(),
+            MenuItems::PullRequests => app_state.pull_requests.query.clone(),
+        };
+
+        match key.code {
+            KeyCode::Char(char) => query.push(char),
+            KeyCode::Backspace => {
+                query.pop();
+            }
+            KeyCode::Enter => app_state.searching = false,
+            KeyCode::Esc => {
+                query.clear();
+                app_state.searching = false;
+            }
+            KeyCode::Up => app_state.previous(),
+            KeyCode::Down => app_state.next(),
+            _ => {}
+        }
+
+        match app_state.current_menu {
+            MenuItems::Issues => app_state.issues.set_query(&query),
+            MenuItems::PullRequests => app_state.pull_requests.set_query(&query),
+        }
+
+        return None;
+    }
+
+    // Preview controls
+    if app_state.focus == Focus::Preview {
+        let handled = match key.code {
+            KeyCode::Up | KeyCode::Char('k') => {
+                app_state.scroll_preview(-1);
+                true
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                app_state.scroll_preview(1);
+                true
             }
+            KeyCode::PageUp => {
+                app_state.scroll_preview(-app_state.half_page());
+                true
+            }
+            KeyCode::PageDown => {
+                app_state.scroll_preview(app_state.half_page());
+                true
+            }
+            KeyCode::Char('u') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                app_state.scroll_preview(-app_state.half_page());
+                true
+            }
+            KeyCode::Char('d') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                app_state.scroll_preview(app_state.half_page());
+                true
+            }
+            KeyCode::Home | KeyCode::Char('g') => {
+                app_state.set_preview_scroll(0);
+                true
+            }
+            KeyCode::End | KeyCode::Char('G') => {
+                app_state.set_preview_scroll(u16::MAX);
+                true
+            }
+            _ => false,
+        };
+
+        if handled {
+            return None;
+        }
+    }
+
+    match key.code {
+        // Menu switcher
+        KeyCode::Char('I') if app_state.current_view.is_some() => {
+            switch_view(app_state, context, None)
+        }
+        KeyCode::Char('I') => app_state.current_menu = MenuItems::Issues,
+        KeyCode::Char(number @ '1'..='9') => {
+            let index = number as usize - '1' as usize;
+
+            if index < app_state.views.len() {
+                if app_state.current_view == Some(index) {
+                    app_state.current_menu = MenuItems::Issues;
+                } else {
+                    switch_view(app_state, context, Some(index));
+                }
+            }
+        }
+        KeyCode::Char('V') => {
+            let name = app_state
+                .current_view
+                .and_then(|index| app_state.views.get(index))
+                .map(|view| view.name.clone())
+                .unwrap_or_default();
+
+            app_state.prompt = Some(Prompt::new(
+                PromptKind::SaveView,
+                "Save the current view as",
+                name,
+            ));
+        }
+        KeyCode::Char('P') => app_state.current_menu = MenuItems::PullRequests,
+        KeyCode::Char('p') => return Some(Action::SwitchProfile),
+
+        // Focus switcher
+        KeyCode::Tab => app_state.toggle_focus(),
+
+        // Issue actions
+        KeyCode::Char('c') => {
+            let repository = app_state
+                .issues
+                .selected_item()
+                .and_then(|issue| issue.repository.as_ref())
+                .map(|repository| repository.full_name.clone())
+                .or(context.config.repository.clone())
+                .unwrap_or_default();
+
+            app_state.prompt = Some(Prompt::new(
+                PromptKind::CreateIssue,
+                "Create issue in (owner/name)",
+                repository,
+            ));
+        }
+
+        KeyCode::Char('s') => {
+            let query = app_state
+                .search_query
+                .as_ref()
+                .map(|search| search.query.clone())
+                .unwrap_or_default();
+
+            app_state.prompt = Some(Prompt::new(
+                PromptKind::SearchQuery,
+                "Github search query (empty for your issues)",
+                query,
+            ));
+        }
+
+        KeyCode::Char('m') if app_state.current_menu == MenuItems::Issues => {
+            return comment_action(app_state)
+        }
+
+        KeyCode::Char('x') if app_state.current_menu == MenuItems::Issues => {
+            confirm_issue_action(app_state, |issue| {
+                if issue.state == "open" {
+                    IssueAction::CloseAsCompleted
+                } else {
+                    IssueAction::Reopen
+                }
+            })
+        }
+        KeyCode::Char('X') if app_state.current_menu == MenuItems::Issues => {
+            confirm_issue_action(app_state, |_| IssueAction::CloseAsNotPlanned)
+        }
+        KeyCode::Char('L') if app_state.current_menu == MenuItems::Issues => {
+            confirm_issue_action(app_state, |issue| {
+                if issue.locked {
+                    IssueAction::Unlock
+                } else {
+                    IssueAction::Lock(None)
+                }
+            })
+        }
+
+        KeyCode::Char('e') if app_state.current_menu == MenuItems::Issues => {
+            return edit_action(app_state)
+        }
+        KeyCode::Char('l') if app_state.current_menu == MenuItems::Issues => {
+            open_label_picker(app_state, context)
+        }
+        KeyCode::Char('a') if app_state.current_menu == MenuItems::Issues => {
+            open_assignee_picker(app_state, context)
+        }
+        KeyCode::Char('M') if app_state.current_menu == MenuItems::Issues => {
+            open_milestone_picker(app_state, context)
+        }
+
+        // Refresh the lists
+        KeyCode::Char('r') => refresh(app_state, context),
+
+        // Show why issues were left out of the list
+        KeyCode::Char('!') if !app_state.skipped_issues.is_empty() => {
+            app_state.error = Some(format!(
+                "These issues could not be parsed:\n{}",
+                app_state.skipped_issues.join("\n")
+            ));
+        }
+
+        // List controls
+        KeyCode::Up | KeyCode::Char('k') => app_state.previous(),
+        KeyCode::Down | KeyCode::Char('j') => app_state.next(),
+        KeyCode::Char('/') => app_state.searching = true,
+        KeyCode::Esc => match app_state.current_menu {
+            MenuItems::Issues => app_state.issues.set_query(""),
+            MenuItems::PullRequests => app_state.pull_requests.set_query(""),
+        },
+        KeyCode::Enter => match app_state.current_menu {
+            MenuItems::Issues => {
+                if let Some(issue) = app_state.issues.selected_item() {
+                    let issue_url = issue.url.clone();
+                    let comments_url = issue.comments_url.clone();
+
+                    app_state.tasks.insert(
+                        Task::Comments(issue_url.clone()),
+                        String::from("Fetching comments.."),
+                    );
+                    spawn_fetch_comments(
+                        context.client.clone(),
+                        context.config.clone(),
+                        context.sender.clone(),
+                        issue_url.clone(),
+                        comments_url.clone(),
+                    );
+                    app_state.detail_view = Some(DetailView::new(issue_url, comments_url));
+                }
+            }
+            MenuItems::PullRequests => {
+                if let Some(pull_request) = app_state.pull_requests.selected_item() {
+                    if let Err(err) = open_in_browser(&pull_request.html_url) {
+                        app_state.error = Some(err.to_string());
+                    }
+                }
+            }
+        },
+        KeyCode::Char('o') => {
+            let html_url = match app_state.current_menu {
+                MenuItems::Issues => app_state
+                    .issues
+                    .selected_item()
+                    .map(|issue| issue.html_url.as_str()),
+                MenuItems::PullRequests => app_state
+                    .pull_requests
+                    .selected_item()
+                    .map(|pull_request| pull_request.html_url.as_str()),
+            };
+
+            if let Some(html_url) = html_url {
+                if let Err(err) = open_in_browser(html_url) {
+                    app_state.error = Some(err.to_string());
+                }
+            }
+        }
+
+        // Exit keys
+        KeyCode::Char('q') => return Some(Action::Quit),
+
+        _ => {}
+    }
+
+    None
+}
+
+/// Fill `picker` from the cached choices of its repository, returns false if there are none.
+fn fill_from_cache<T: Searchable + Clone>(
+    picker: &mut Picker<T>,
+    cache: &HashMap<String, Vec<T>>,
+) -> bool {
+    match cache.get(&picker.repository) {
+        Some(items) => {
+            picker.set_items(items.clone());
+            true
+        }
+        None => false,
+    }
+}
+
+/// Cache the choices fetched for `repository` and fill the open `picker` with them.
+fn choices_fetched<T: Searchable + Clone>(
+    picker: Option<&mut Picker<T>>,
+    cache: &mut HashMap<String, Vec<T>>,
+    repository: String,
+    result: Result<Vec<T>>,
+) -> Result<()> {
+    let items = result?;
+
+    if let Some(picker) = picker.filter(|picker| picker.repository == repository) {
+        picker.set_items(items.clone());
+    }
+
+    cache.insert(repository, items);
+    Ok(())
+}
+
+/// Open the label picker for the selected issue, fetching the repository's labels unless
+/// they are cached.
+fn open_label_picker(app_state: &mut AppState, context: &Context) {
+    let Some(issue) = app_state.issues.selected_item() else {
+        return;
+    };
+
+    let mut picker = Picker::new(
+        "Labels",
+        issue.url.clone(),
+        issue.repository_name(),
+        issue
+            .labels
+            .iter()
+            .map(|label| label.name.clone())
+            .collect(),
+        |label: &Label| label.name.clone(),
+    );
+
+    if !fill_from_cache(&mut picker, &app_state.labels) {
+        app_state.tasks.insert(
+            Task::Labels,
+            format!("Fetching labels of {}..", picker.repository),
+        );
+        spawn_fetch_labels(
+            context.client.clone(),
+            context.config.clone(),
+            context.sender.clone(),
+            picker.repository.clone(),
+        );
+    }
+
+    app_state.picker = Some(IssuePicker::Labels(picker));
+}
+
+/// Open the assignee picker for the selected issue, fetching the repository's assignable
+/// users unless they are cached.
+fn open_assignee_picker(app_state: &mut AppState, context: &Context) {
+    let Some(issue) = app_state.issues.selected_item() else {
+        return;
+    };
+
+    let mut picker = Picker::new(
+        "Assignees",
+        issue.url.clone(),
+        issue.repository_name(),
+        issue
+            .assignees
+            .iter()
+            .map(|user| user.login.clone())
+            .collect(),
+        |user: &User| user.login.clone(),
+    );
+
+    if !fill_from_cache(&mut picker, &app_state.assignees) {
+        app_state.tasks.insert(
+            Task::Assignees,
+            format!("Fetching assignees of {}..", picker.repository),
+        );
+        spawn_fetch_assignees(
+            context.client.clone(),
+            context.config.clone(),
+            context.sender.clone(),
+            picker.repository.clone(),
+        );
+    }
+
+    app_state.picker = Some(IssuePicker::Assignees(picker));
+}
+
+/// Open the milestone picker for the selected issue, fetching the repository's open
+/// milestones unless they are cached.
+fn open_milestone_picker(app_state: &mut AppState, context: &Context) {
+    let Some(issue) = app_state.issues.selected_item() else {
+        return;
+    };
+
+    let mut picker = Picker::new(
+        "Milestone",
+        issue.url.clone(),
+        issue.repository_name(),
+        issue
+            .milestone
+            .iter()
+            .map(|milestone| milestone.number.to_string())
+            .collect(),
+        |milestone: &Milestone| milestone.number.to_string(),
+    )
+    .single();
+
+    if !fill_from_cache(&mut picker, &app_state.milestones) {
+        app_state.tasks.insert(
+            Task::Milestones,
+            format!("Fetching milestones of {}..", picker.repository),
+        );
+        spawn_fetch_milestones(
+            context.client.clone(),
+            context.config.clone(),
+            context.sender.clone(),
+            picker.repository.clone(),
+        );
+    }
+
+    app_state.picker = Some(IssuePicker::Milestone(picker));
+}
+
+/// Handle a key press in `picker`, returns true once the choice was confirmed.
+fn picker_key<T: Searchable>(picker: &mut Picker<T>, key: KeyEvent) -> bool {
+    match key.code {
+        KeyCode::Up => picker.list.previous(),
+        KeyCode::Down => picker.list.next(),
+        KeyCode::Char(' ') => picker.toggle(),
+        KeyCode::Char(char) => {
+            let query = format!("{}{}", picker.list.query, char);
+            picker.list.set_query(&query);
+        }
+        KeyCode::Backspace => {
+            let mut query = picker.list.query.clone();
+            query.pop();
+            picker.list.set_query(&query);
+        }
+        KeyCode::Enter => return true,
+        _ => {}
+    }
+
+    false
+}
+
+/// Apply the choices checked in `picker` to its issue.
+fn apply_picker(app_state: &mut AppState, context: &Context, picker: IssuePicker) {
+    match picker {
+        IssuePicker::Labels(picker) => {
+            app_state.tasks.insert(
+                Task::UpdateIssue(picker.issue_url.clone()),
+                String::from("Setting labels.."),
+            );
+            spawn_set_issue_labels(
+                context.client.clone(),
+                context.config.clone(),
+                context.sender.clone(),
+                picker.issue_url,
+                picker.checked,
+            );
+        }
+        IssuePicker::Assignees(picker) => {
+            app_state.tasks.insert(
+                Task::UpdateIssue(picker.issue_url.clone()),
+                String::from("Setting assignees.."),
+            );
+            spawn_update_issue(
+                context.client.clone(),
+                context.config.clone(),
+                context.sender.clone(),
+                picker.issue_url,
+                serde_json::json!({ "assignees": picker.checked }),
+            );
         }
+        IssuePicker::Milestone(picker) => {
+            // No milestone clears it
+            let milestone = picker
+                .checked
+                .first()
+                .and_then(|number| number.parse::<usize>().ok());
+
+            app_state.tasks.insert(
+                Task::UpdateIssue(picker.issue_url.clone()),
+                String::from("Setting milestone.."),
+            );
+            spawn_update_issue(
+                context.client.clone(),
+                context.config.clone(),
+                context.sender.clone(),
+                picker.issue_url,
+                serde_json::json!({ "milestone": milestone }),
+            );
+        }
+    }
+}
+
+/// Ask to confirm the action `action` picks for the selected issue.
+fn confirm_issue_action(app_state: &mut AppState, action: impl Fn(&Issue) -> IssueAction) {
+    if let Some(issue) = app_state.issues.selected_item() {
+        app_state.confirmation = Some(Confirmation {
+            action: action(issue),
+            issue_url: issue.url.clone(),
+            issue_name: format!("#{} {}", issue.number, issue.title),
+        });
+    }
+}
+
+/// Send a confirmed issue action in the background.
+fn apply_issue_action(app_state: &mut AppState, context: &Context, confirmation: Confirmation) {
+    app_state.tasks.insert(
+        Task::UpdateIssue(confirmation.issue_url.clone()),
+        format!("{} {}..", confirmation.action, confirmation.issue_name),
+    );
+
+    let (client, config, sender) = (
+        context.client.clone(),
+        context.config.clone(),
+        context.sender.clone(),
+    );
+    let issue_url = confirmation.issue_url;
+
+    match confirmation.action {
+        IssueAction::CloseAsCompleted => spawn_update_issue(
+            client,
+            config,
+            sender,
+            issue_url,
+            serde_json::json!({ "state": "closed", "state_reason": "completed" }),
+        ),
+        IssueAction::CloseAsNotPlanned => spawn_update_issue(
+            client,
+            config,
+            sender,
+            issue_url,
+            serde_json::json!({ "state": "closed", "state_reason": "not_planned" }),
+        ),
+        IssueAction::Reopen => spawn_update_issue(
+            client,
+            config,
+            sender,
+            issue_url,
+            serde_json::json!({ "state": "open", "state_reason": "reopened" }),
+        ),
+        IssueAction::Lock(reason) => spawn_set_issue_lock(
+            client,
+            config,
+            sender,
+            issue_url,
+            true,
+            reason.map(|index| LOCK_REASONS[index].to_string()),
+        ),
+        IssueAction::Unlock => spawn_set_issue_lock(client, config, sender, issue_url, false, None),
     }
 }
+
+/// Reply to the current issue, reusing a draft left behind by a failed post.
+fn comment_action(app_state: &AppState) -> Option<Action> {
+    let issue = app_state.current_issue()?;
+    let repository = issue
+        .repository
+        .as_ref()
+        .map_or(String::from("issue"), |repository| {
+            repository.full_name.replace('/', "-")
+        });
+
+    Some(Action::CreateComment {
+        comments_url: issue.comments_url.clone(),
+        draft_name: format!("itg-comment-{}-{}.md", repository, issue.number),
+    })
+}
+
+/// Write a comment in the editor and post it in the background. An empty comment cancels.
+fn create_comment_in_editor<B: Backend>(
+    terminal: &mut Terminal<B>,
+    input_pause: &InputPause,
+    app_state: &mut AppState,
+    context: &Context,
+    comments_url: String,
+    draft_name: String,
+) -> Result<()> {
+    let edited = with_suspended_terminal(terminal, input_pause, || {
+        let draft = Draft::open_or_create(&draft_name, "")?;
+        let content = draft.edit()?;
+
+        Ok((draft, content))
+    })?;
+
+    match edited {
+        Ok((draft, content)) if content.trim().is_empty() => draft.discard(),
+        Ok((draft, content)) => {
+            app_state
+                .tasks
+                .insert(Task::CreateComment, String::from("Posting comment.."));
+            spawn_create_comment(
+                context.client.clone(),
+                context.config.clone(),
+                context.sender.clone(),
+                comments_url,
+                content.trim().to_string(),
+                draft,
+            );
+        }
+        Err(err) => app_state.error = Some(err.to_string()),
+    }
+
+    Ok(())
+}
+
+/// Edit the title and body of the current issue.
+fn edit_action(app_state: &AppState) -> Option<Action> {
+    let issue = app_state.current_issue()?;
+
+    Some(Action::EditIssue {
+        issue_url: issue.url.clone(),
+    })
+}
+
+/// Edit the title and body of an issue in the editor and save the changes in the background,
+/// reusing a draft left behind by a failed or conflicting save.
+fn edit_issue_in_editor<B: Backend>(
+    terminal: &mut Terminal<B>,
+    input_pause: &InputPause,
+    app_state: &mut AppState,
+    context: &Context,
+    issue_url: String,
+) -> Result<()> {
+    let Some(issue) = app_state
+        .issues
+        .items
+        .iter()
+        .find(|issue| issue.url == issue_url)
+    else {
+        return Ok(());
+    };
+
+    let draft_name = format!(
+        "itg-edit-{}-{}.md",
+        issue.repository_name().replace('/', "-"),
+        issue.number
+    );
+    let template = IssueEdit::template(issue);
+
+    let edited = with_suspended_terminal(terminal, input_pause, || {
+        let draft = Draft::open_or_create(&draft_name, &template)?;
+
+        // A kept draft was written against the issue as it was then, which the conflict check
+        // compares with the server instead of the issue in the list
+        let opened_at = match draft.base().and_then(|base| base.parse().ok()) {
+            Some(opened_at) => opened_at,
+            None => {
+                draft.set_base(&issue.updated_at.to_rfc3339())?;
+                issue.updated_at
+            }
+        };
+        let content = draft.edit()?;
+
+        Ok((draft, opened_at, content))
+    })?;
+
+    match edited {
+        Ok((draft, opened_at, content)) => match IssueEdit::parse(&content, issue) {
+            Ok(edit) if edit.is_empty() => draft.discard(),
+            Ok(edit) => {
+                app_state.tasks.insert(
+                    Task::UpdateIssue(issue_url.clone()),
+                    format!("Saving #{} {}..", issue.number, issue.title),
+                );
+                spawn_edit_issue(
+                    context.client.clone(),
+                    context.config.clone(),
+                    context.sender.clone(),
+                    issue_url,
+                    edit,
+                    Some(opened_at),
+                    draft,
+                );
+            }
+            // The draft is reopened on the next edit, so the fix is a small one
+            Err(err) => {
+                app_state.error = Some(format!(
+                    "{} (the draft is kept at {})",
+                    err,
+                    draft.path.display()
+                ))
+            }
+        },
+        Err(err) => app_state.error = Some(err.to_string()),
+    }
+
+    Ok(())
+}
+
+/// Write a new issue in the editor and create it in the background, reusing a draft left
+/// behind by a failed create.
+fn create_issue_in_editor<B: Backend>(
+    terminal: &mut Terminal<B>,
+    input_pause: &InputPause,
+    app_state: &mut AppState,
+    context: &Context,
+    repository: String,
+) -> Result<()> {
+    let edited = with_suspended_terminal(terminal, input_pause, || {
+        let draft = Draft::open_or_create(&new_issue_draft_name(&repository), NEW_ISSUE_TEMPLATE)?;
+        let content = draft.edit()?;
+
+        Ok((draft, content))
+    })?;
+
+    match edited {
+        Ok((draft, content)) => match NewIssue::parse(&content) {
+            Ok(new_issue) => {
+                app_state.tasks.insert(
+                    Task::CreateIssue,
+                    format!("Creating issue in {}..", repository),
+                );
+                spawn_create_issue(
+                    context.client.clone(),
+                    context.config.clone(),
+                    context.sender.clone(),
+                    repository,
+                    new_issue,
+                    draft,
+                );
+            }
+            Err(err) => {
+                app_state.error = Some(format!(
+                    "{} (the draft is kept at {})",
+                    err,
+                    draft.path.display()
+                ))
+            }
+        },
+        Err(err) => app_state.error = Some(err.to_string()),
+    }
+
+    Ok(())
+}
+
+/// Hand the terminal to `f`, e.g. to run an editor, and take it back afterwards.
+fn with_suspended_terminal<B: Backend, T>(
+    terminal: &mut Terminal<B>,
+    input_pause: &InputPause,
+    f: impl FnOnce() -> Result<T>,
+) -> Result<Result<T>> {
+    input_pause.pause();
+    reset_terminal()?;
+
+    let result = f();
+
+    restore_terminal()?;
+    terminal.clear()?;
+    terminal.hide_cursor()?;
+    input_pause.resume();
+
+    Ok(result)
+}
+
+fn open_in_browser(html_url: &str) -> Result<()> {
+    webbrowser::open(html_url).map_err(Error::Browser)
+}
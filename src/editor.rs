@@ -0,0 +1,115 @@
+use std::{
+    env,
+    fs::{self, DirBuilder},
+    path::PathBuf,
+    process::Command,
+};
+
+use crate::error::{Error, Result};
+
+/// The directory drafts are kept in, next to the config file. Unlike the shared temporary
+/// directory, other users can neither read the drafts nor plant files at their paths.
+fn drafts_dir() -> Result<PathBuf> {
+    let config_path = confy::get_configuration_file_path("issue-tracker", None)?;
+    let dir = config_path
+        .parent()
+        .ok_or_else(|| Error::Config(String::from("The config file has no parent directory")))?
+        .join("drafts");
+
+    let mut builder = DirBuilder::new();
+    builder.recursive(true);
+    #[cfg(unix)]
+    std::os::unix::fs::DirBuilderExt::mode(&mut builder, 0o700);
+    builder.create(&dir)?;
+
+    Ok(dir)
+}
+
+/// The file name of the draft of a new issue in `repository`, given as `owner/name`.
+pub fn new_issue_draft_name(repository: &str) -> String {
+    format!("itg-new-issue-{}.md", repository.replace('/', "-"))
+}
+
+/// A text file edited with the user's `$VISUAL` or `$EDITOR`.
+///
+/// The file stays on disk until it is discarded, so a draft is not lost when sending it fails.
+pub struct Draft {
+    pub path: PathBuf,
+}
+
+impl Draft {
+    /// Create the draft `file_name` in the drafts directory, filled with `initial`.
+    pub fn new(file_name: &str, initial: &str) -> Result<Self> {
+        let path = drafts_dir()?.join(file_name);
+        fs::write(&path, initial)?;
+
+        Ok(Self { path })
+    }
+
+    /// Reopen the draft `file_name` left behind by an earlier failure, or create it with
+    /// `initial`.
+    pub fn open_or_create(file_name: &str, initial: &str) -> Result<Self> {
+        let path = drafts_dir()?.join(file_name);
+
+        if path.exists() {
+            Ok(Self { path })
+        } else {
+            Self::new(file_name, initial)
+        }
+    }
+
+    /// Open the draft in the editor and return its content once the editor exits.
+    ///
+    /// The terminal has to be suspended by the caller.
+    pub fn edit(&self) -> Result<String> {
+        let editor = env::var("VISUAL")
+            .or_else(|_| env::var("EDITOR"))
+            .unwrap_or(String::from("vi"));
+
+        // The editor variable can carry arguments, e.g. `code --wait`
+        let mut parts = editor.split_whitespace();
+        let program = parts
+            .next()
+            .ok_or_else(|| Error::Editor(String::from("No editor set in $EDITOR")))?;
+
+        let status = Command::new(program)
+            .args(parts)
+            .arg(&self.path)
+            .status()
+            .map_err(|err| Error::Editor(format!("Failed to launch {}: {}", program, err)))?;
+
+        if !status.success() {
+            return Err(Error::Editor(format!("{} exited with {}", program, status)));
+        }
+
+        Ok(fs::read_to_string(&self.path)?)
+    }
+
+    /// The current content of the draft.
+    pub fn read(&self) -> Result<String> {
+        Ok(fs::read_to_string(&self.path)?)
+    }
+
+    /// Keep `base`, the version of what the draft was written against, next to the draft.
+    pub fn set_base(&self, base: &str) -> Result<()> {
+        fs::write(self.base_path(), base)?;
+        Ok(())
+    }
+
+    /// The base kept with `set_base`, if one was.
+    pub fn base(&self) -> Option<String> {
+        fs::read_to_string(self.base_path()).ok()
+    }
+
+    fn base_path(&self) -> PathBuf {
+        let mut path = self.path.clone().into_os_string();
+        path.push(".base");
+        path.into()
+    }
+
+    /// Remove the draft from disk.
+    pub fn discard(self) {
+        let _ = fs::remove_file(&self.path);
+        let _ = fs::remove_file(self.base_path());
+    }
+}
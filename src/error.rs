@@ -0,0 +1,56 @@
+use std::io;
+
+use chrono::{DateTime, Local};
+use thiserror::Error;
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// Everything that can go wrong in itg, by where it went wrong.
+#[derive(Debug, Error)]
+pub enum Error {
+    /// The config file could not be read or written, or lacks a value
+    #[error("{0}")]
+    Config(String),
+    /// No usable access token, or logging in failed
+    #[error("{0}")]
+    Auth(String),
+    /// A request failed or Github answered with an error status
+    #[error("{0}")]
+    Network(reqwest::Error),
+    /// The rate limit of the token is used up until `reset`
+    #[error("The Github rate limit is exceeded until {}", .reset.format("%H:%M:%S"))]
+    RateLimit { reset: DateTime<Local> },
+    /// A response, url or edited draft could not be parsed
+    #[error("{0}")]
+    Parse(String),
+    #[error("Failed to open the browser: {0}")]
+    Browser(io::Error),
+    /// The editor could not be launched or did not exit successfully
+    #[error("{0}")]
+    Editor(String),
+    /// A draft or the terminal could not be read or written
+    #[error("{0}")]
+    Io(#[from] io::Error),
+}
+
+impl From<reqwest::Error> for Error {
+    fn from(err: reqwest::Error) -> Self {
+        if err.is_decode() {
+            Error::Parse(format!("Unexpected response: {}", err))
+        } else {
+            Error::Network(err)
+        }
+    }
+}
+
+impl From<serde_json::Error> for Error {
+    fn from(err: serde_json::Error) -> Self {
+        Error::Parse(format!("Unexpected response: {}", err))
+    }
+}
+
+impl From<confy::ConfyError> for Error {
+    fn from(err: confy::ConfyError) -> Self {
+        Error::Config(err.to_string())
+    }
+}
@@ -0,0 +1,504 @@
+use std::{
+    sync::{Arc, Condvar, Mutex},
+    thread,
+    time::Duration,
+};
+
+use chrono::{DateTime, Utc};
+use crossterm::event::{self, Event, KeyEvent};
+use tokio::sync::mpsc::UnboundedSender;
+
+use crate::{
+    controls::load_next_profile,
+    create_comment, create_issue,
+    editor::Draft,
+    error::Result,
+    fetch_assignees, fetch_comments, fetch_issue, fetch_issues, fetch_labels, fetch_milestones,
+    fetch_pull_requests,
+    http::GithubClient,
+    models::{
+        comment::Comment, config::Config, filters::Filters, issue::Issue, issue_edit::IssueEdit,
+        label::Label, milestone::Milestone, new_issue::NewIssue, pull_request::PullRequest,
+        search_query::SearchQuery, user::User,
+    },
+    search_issues, set_issue_labels, set_issue_lock, update_issue, Fetched, SearchPage,
+};
+
+/// Everything the event loop reacts to, merged into one channel.
+pub enum AppEvent {
+    Key(KeyEvent),
+    Resize,
+    Tick,
+    /// Time to refresh the lists in the background
+    Refresh,
+    /// Progress message of a running network task
+    Progress(Task, String),
+    /// Issues fetched for the issue list of `AppState::issues_generation` `generation`
+    Issues {
+        generation: u64,
+        result: Result<Fetched<Issue>>,
+    },
+    PullRequests(Result<Vec<PullRequest>>),
+    /// A page of results of the search `query`, appended to the list if `append` is set
+    Search {
+        query: String,
+        append: bool,
+        result: Result<SearchPage>,
+    },
+    Comments {
+        issue_url: String,
+        result: Result<Vec<Comment>>,
+    },
+    IssueCreated(Result<Box<Issue>>, Draft),
+    IssueUpdated {
+        issue_url: String,
+        result: Result<Box<Issue>>,
+    },
+    /// An edit was saved, or failed and its draft is kept
+    IssueEdited {
+        issue_url: String,
+        result: Result<Box<Issue>>,
+        draft: Draft,
+    },
+    /// The issue changed on the server since it was opened for editing
+    EditConflict {
+        remote: Box<Issue>,
+        edit: IssueEdit,
+        draft: Draft,
+    },
+    IssueLockChanged {
+        issue_url: String,
+        locked: bool,
+        reason: Option<String>,
+        result: Result<()>,
+    },
+    Labels {
+        repository: String,
+        result: Result<Vec<Label>>,
+    },
+    Assignees {
+        repository: String,
+        result: Result<Vec<User>>,
+    },
+    Milestones {
+        repository: String,
+        result: Result<Vec<Milestone>>,
+    },
+    IssueLabelsSet {
+        issue_url: String,
+        result: Result<Vec<Label>>,
+    },
+    CommentCreated {
+        comments_url: String,
+        result: Result<Comment>,
+        draft: Draft,
+    },
+    /// The profile after the current one was loaded, to switch to
+    ProfileLoaded(Result<Config>),
+}
+
+/// The network tasks that can run in the background. Tasks about a single issue are keyed by
+/// its API url, so that they can run for several issues at once.
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub enum Task {
+    Issues,
+    PullRequests,
+    Comments(String),
+    CreateIssue,
+    CreateComment,
+    UpdateIssue(String),
+    Search,
+    Labels,
+    Assignees,
+    Milestones,
+    Profile,
+}
+
+/// How long pausing waits for the input reader to stop polling, in case it hangs.
+const PAUSE_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// Pauses the input reader while the terminal is handed to another program, so it does not
+/// steal that program's key presses.
+#[derive(Clone, Default)]
+pub struct InputPause {
+    state: Arc<(Mutex<PauseState>, Condvar)>,
+}
+
+#[derive(Default)]
+struct PauseState {
+    paused: bool,
+    /// Set by the reader while it polls the terminal, cleared when it waits or has exited
+    polling: bool,
+}
+
+impl InputPause {
+    /// Pause the reader and wait until it stopped polling.
+    pub fn pause(&self) {
+        let (lock, condvar) = &*self.state;
+        let mut state = lock.lock().unwrap();
+        state.paused = true;
+
+        let _ = condvar
+            .wait_timeout_while(state, PAUSE_TIMEOUT, |state| state.polling)
+            .unwrap();
+    }
+
+    pub fn resume(&self) {
+        let (lock, condvar) = &*self.state;
+        lock.lock().unwrap().paused = false;
+        condvar.notify_all();
+    }
+
+    /// Called by the reader before each poll, blocks while paused.
+    fn wait_until_resumed(&self) {
+        let (lock, condvar) = &*self.state;
+        let mut state = lock.lock().unwrap();
+        state.polling = false;
+        condvar.notify_all();
+
+        let mut state = condvar.wait_while(state, |state| state.paused).unwrap();
+        state.polling = true;
+    }
+
+    /// Called by the reader when it exits, so that pausing does not wait for it.
+    fn stop(&self) {
+        let (lock, condvar) = &*self.state;
+        lock.lock().unwrap().polling = false;
+        condvar.notify_all();
+    }
+}
+
+/// Read terminal events on a dedicated thread, since crossterm's reads are blocking.
+pub fn spawn_input_reader(sender: UnboundedSender<AppEvent>) -> InputPause {
+    let pause = InputPause::default();
+    let reader_pause = pause.clone();
+
+    thread::spawn(move || {
+        read_input(&sender, &reader_pause);
+        reader_pause.stop();
+    });
+
+    pause
+}
+
+/// Send the terminal events to `sender` until the loop has exited or reading fails.
+fn read_input(sender: &UnboundedSender<AppEvent>, pause: &InputPause) {
+    loop {
+        pause.wait_until_resumed();
+
+        // Poll with a timeout so the thread notices when the loop has exited or was paused
+        match event::poll(Duration::from_millis(100)) {
+            Ok(true) => {
+                let app_event = match event::read() {
+                    Ok(Event::Key(key)) => AppEvent::Key(key),
+                    Ok(Event::Resize(_, _)) => AppEvent::Resize,
+                    Ok(_) => continue,
+                    Err(_) => return,
+                };
+
+                if sender.send(app_event).is_err() {
+                    return;
+                }
+            }
+            Ok(false) => {
+                if sender.is_closed() {
+                    return;
+                }
+            }
+            Err(_) => return,
+        }
+    }
+}
+
+/// Send `event` every `period`, until the loop has exited.
+pub fn spawn_interval(
+    sender: UnboundedSender<AppEvent>,
+    period: Duration,
+    event: fn() -> AppEvent,
+) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(period);
+        // The first tick completes immediately
+        interval.tick().await;
+
+        loop {
+            interval.tick().await;
+
+            if sender.send(event()).is_err() {
+                return;
+            }
+        }
+    });
+}
+
+pub fn spawn_fetch_issues(
+    client: GithubClient,
+    config: Arc<Config>,
+    sender: UnboundedSender<AppEvent>,
+    generation: u64,
+    filters: Filters,
+) {
+    tokio::spawn(async move {
+        let result = fetch_issues(&client, &config, &filters, |page| {
+            let _ = sender.send(AppEvent::Progress(
+                Task::Issues,
+                format!("Fetching issues… page {}", page),
+            ));
+        })
+        .await;
+
+        let _ = sender.send(AppEvent::Issues { generation, result });
+    });
+}
+
+pub fn spawn_fetch_pull_requests(
+    client: GithubClient,
+    config: Arc<Config>,
+    sender: UnboundedSender<AppEvent>,
+) {
+    tokio::spawn(async move {
+        let result = fetch_pull_requests(&client, &config).await;
+
+        let _ = sender.send(AppEvent::PullRequests(result));
+    });
+}
+
+/// Fetch the first `pages` pages of results of `search`, or as many pages from `next_url` on to
+/// continue it.
+pub fn spawn_search_issues(
+    client: GithubClient,
+    config: Arc<Config>,
+    sender: UnboundedSender<AppEvent>,
+    search: &SearchQuery,
+    next_url: Option<String>,
+    pages: usize,
+) {
+    let (query, sort, direction) = (search.query.clone(), search.sort, search.direction);
+
+    tokio::spawn(async move {
+        let append = next_url.is_some();
+        let result =
+            search_issues(&client, &config, &query, sort, direction, next_url, pages).await;
+
+        let _ = sender.send(AppEvent::Search {
+            query,
+            append,
+            result,
+        });
+    });
+}
+
+/// Fetch the comments at `comments_url` of the issue at `issue_url`.
+pub fn spawn_fetch_comments(
+    client: GithubClient,
+    config: Arc<Config>,
+    sender: UnboundedSender<AppEvent>,
+    issue_url: String,
+    comments_url: String,
+) {
+    tokio::spawn(async move {
+        let result = fetch_comments(&client, &config, &comments_url).await;
+
+        let _ = sender.send(AppEvent::Comments { issue_url, result });
+    });
+}
+
+/// Create an issue, handing back the draft so it can be kept if creating failed.
+pub fn spawn_create_issue(
+    client: GithubClient,
+    config: Arc<Config>,
+    sender: UnboundedSender<AppEvent>,
+    repository: String,
+    new_issue: NewIssue,
+    draft: Draft,
+) {
+    tokio::spawn(async move {
+        let result = create_issue(&client, &config, &repository, &new_issue)
+            .await
+            .map(Box::new);
+
+        let _ = sender.send(AppEvent::IssueCreated(result, draft));
+    });
+}
+
+/// Post a comment, handing back the draft so it can be kept if posting failed.
+pub fn spawn_create_comment(
+    client: GithubClient,
+    config: Arc<Config>,
+    sender: UnboundedSender<AppEvent>,
+    comments_url: String,
+    body: String,
+    draft: Draft,
+) {
+    tokio::spawn(async move {
+        let result = create_comment(&client, &config, &comments_url, &body).await;
+
+        let _ = sender.send(AppEvent::CommentCreated {
+            comments_url,
+            result,
+            draft,
+        });
+    });
+}
+
+pub fn spawn_fetch_issue(
+    client: GithubClient,
+    config: Arc<Config>,
+    sender: UnboundedSender<AppEvent>,
+    issue_url: String,
+) {
+    tokio::spawn(async move {
+        let result = fetch_issue(&client, &config, &issue_url)
+            .await
+            .map(Box::new);
+
+        let _ = sender.send(AppEvent::IssueUpdated { issue_url, result });
+    });
+}
+
+pub fn spawn_update_issue(
+    client: GithubClient,
+    config: Arc<Config>,
+    sender: UnboundedSender<AppEvent>,
+    issue_url: String,
+    changes: serde_json::Value,
+) {
+    tokio::spawn(async move {
+        let result = update_issue(&client, &config, &issue_url, &changes)
+            .await
+            .map(Box::new);
+
+        let _ = sender.send(AppEvent::IssueUpdated { issue_url, result });
+    });
+}
+
+/// Save `edit`, unless the issue was updated on the server after `opened_at`. Without
+/// `opened_at` the edit overwrites the issue.
+pub fn spawn_edit_issue(
+    client: GithubClient,
+    config: Arc<Config>,
+    sender: UnboundedSender<AppEvent>,
+    issue_url: String,
+    edit: IssueEdit,
+    opened_at: Option<DateTime<Utc>>,
+    draft: Draft,
+) {
+    tokio::spawn(async move {
+        if let Some(opened_at) = opened_at {
+            match fetch_issue(&client, &config, &issue_url).await {
+                Ok(remote) if remote.updated_at != opened_at => {
+                    let _ = sender.send(AppEvent::EditConflict {
+                        remote: Box::new(remote),
+                        edit,
+                        draft,
+                    });
+                    return;
+                }
+                Ok(_) => {}
+                Err(err) => {
+                    let _ = sender.send(AppEvent::IssueEdited {
+                        issue_url,
+                        result: Err(err),
+                        draft,
+                    });
+                    return;
+                }
+            }
+        }
+
+        let result = update_issue(&client, &config, &issue_url, &edit)
+            .await
+            .map(Box::new);
+
+        let _ = sender.send(AppEvent::IssueEdited {
+            issue_url,
+            result,
+            draft,
+        });
+    });
+}
+
+pub fn spawn_set_issue_lock(
+    client: GithubClient,
+    config: Arc<Config>,
+    sender: UnboundedSender<AppEvent>,
+    issue_url: String,
+    locked: bool,
+    reason: Option<String>,
+) {
+    tokio::spawn(async move {
+        let result = set_issue_lock(&client, &config, &issue_url, locked, reason.as_deref()).await;
+
+        let _ = sender.send(AppEvent::IssueLockChanged {
+            issue_url,
+            locked,
+            reason,
+            result,
+        });
+    });
+}
+
+pub fn spawn_fetch_labels(
+    client: GithubClient,
+    config: Arc<Config>,
+    sender: UnboundedSender<AppEvent>,
+    repository: String,
+) {
+    tokio::spawn(async move {
+        let result = fetch_labels(&client, &config, &repository).await;
+
+        let _ = sender.send(AppEvent::Labels { repository, result });
+    });
+}
+
+pub fn spawn_fetch_assignees(
+    client: GithubClient,
+    config: Arc<Config>,
+    sender: UnboundedSender<AppEvent>,
+    repository: String,
+) {
+    tokio::spawn(async move {
+        let result = fetch_assignees(&client, &config, &repository).await;
+
+        let _ = sender.send(AppEvent::Assignees { repository, result });
+    });
+}
+
+pub fn spawn_fetch_milestones(
+    client: GithubClient,
+    config: Arc<Config>,
+    sender: UnboundedSender<AppEvent>,
+    repository: String,
+) {
+    tokio::spawn(async move {
+        let result = fetch_milestones(&client, &config, &repository).await;
+
+        let _ = sender.send(AppEvent::Milestones { repository, result });
+    });
+}
+
+pub fn spawn_set_issue_labels(
+    client: GithubClient,
+    config: Arc<Config>,
+    sender: UnboundedSender<AppEvent>,
+    issue_url: String,
+    labels: Vec<String>,
+) {
+    tokio::spawn(async move {
+        let result = set_issue_labels(&client, &config, &issue_url, &labels).await;
+
+        let _ = sender.send(AppEvent::IssueLabelsSet { issue_url, result });
+    });
+}
+
+pub fn spawn_load_next_profile(
+    client: GithubClient,
+    config: Arc<Config>,
+    sender: UnboundedSender<AppEvent>,
+) {
+    tokio::spawn(async move {
+        let result = load_next_profile(&client, &config).await;
+
+        let _ = sender.send(AppEvent::ProfileLoaded(result));
+    });
+}
@@ -0,0 +1,141 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// Find the host and `owner/name` of the repository checked out at `dir` or one of its parents,
+/// from its `origin` remote, or its `upstream` remote if there is no origin.
+pub fn detect_repository(dir: &Path) -> Option<(String, String)> {
+    let git_dir = find_git_dir(dir)?;
+    let config = fs::read_to_string(common_dir(&git_dir).join("config")).ok()?;
+
+    ["origin", "upstream"]
+        .iter()
+        .find_map(|remote| remote_url(&config, remote))
+        .and_then(|url| repository_from_url(&url))
+}
+
+/// The git directory of the checkout containing `dir`.
+fn find_git_dir(dir: &Path) -> Option<PathBuf> {
+    for dir in dir.ancestors() {
+        let dot_git = dir.join(".git");
+
+        if dot_git.is_dir() {
+            return Some(dot_git);
+        }
+
+        // Worktrees and submodules have a `.git` file pointing to their git directory
+        if dot_git.is_file() {
+            let content = fs::read_to_string(&dot_git).ok()?;
+            let git_dir = content.trim().strip_prefix("gitdir:")?.trim();
+
+            return Some(dir.join(git_dir));
+        }
+    }
+
+    None
+}
+
+/// Worktrees share the config of the main git directory, named in their `commondir` file.
+fn common_dir(git_dir: &Path) -> PathBuf {
+    match fs::read_to_string(git_dir.join("commondir")) {
+        Ok(common_dir) => git_dir.join(common_dir.trim()),
+        Err(_) => git_dir.to_path_buf(),
+    }
+}
+
+/// The url of `remote` in the contents of a git config file.
+fn remote_url(config: &str, remote: &str) -> Option<String> {
+    let section = format!("[remote \"{}\"]", remote);
+    let mut in_section = false;
+
+    for line in config.lines().map(str::trim) {
+        if line.starts_with('[') {
+            in_section = line == section;
+            continue;
+        }
+
+        if !in_section {
+            continue;
+        }
+
+        if let Some((key, value)) = line.split_once('=') {
+            if key.trim() == "url" {
+                return Some(value.trim().trim_matches('"').to_string());
+            }
+        }
+    }
+
+    None
+}
+
+/// The host and `owner/name` of a remote url in SSH (`git@host:owner/name.git`,
+/// `ssh://git@host/owner/name`) or HTTPS (`https://host/owner/name.git`) form.
+fn repository_from_url(url: &str) -> Option<(String, String)> {
+    let (authority, path) = match url.split_once("://") {
+        Some((_, rest)) => rest.split_once('/')?,
+        None => url.split_once(':')?,
+    };
+
+    // Drop the user and port around the host
+    let host = authority.rsplit('@').next()?.split(':').next()?;
+    if host.is_empty() {
+        return None;
+    }
+
+    let mut parts = path
+        .trim_end_matches('/')
+        .trim_end_matches(".git")
+        .rsplit('/');
+    let name = parts.next().filter(|name| !name.is_empty())?;
+    let owner = parts.next().filter(|owner| !owner.is_empty())?;
+
+    Some((host.to_string(), format!("{}/{}", owner, name)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_ssh_and_https_remotes() {
+        for (url, host) in [
+            ("git@github.com:Nickiel12/itg.git", "github.com"),
+            ("ssh://git@github.com/Nickiel12/itg", "github.com"),
+            (
+                "ssh://git@github.example.com:2222/Nickiel12/itg.git",
+                "github.example.com",
+            ),
+            ("https://github.com/Nickiel12/itg.git", "github.com"),
+            ("https://gitlab.com/Nickiel12/itg/", "gitlab.com"),
+        ] {
+            assert_eq!(
+                repository_from_url(url),
+                Some((host.to_string(), String::from("Nickiel12/itg"))),
+                "{}",
+                url
+            );
+        }
+
+        assert_eq!(repository_from_url("/srv/git/itg.git"), None);
+    }
+
+    #[test]
+    fn finds_the_remote_url() {
+        let config = r#"
+[core]
+	bare = false
+[remote "fork"]
+	url = git@github.com:someone/itg.git
+[remote "upstream"]
+	url = https://github.com/Nickiel12/itg.git
+	fetch = +refs/heads/*:refs/remotes/upstream/*
+"#;
+
+        assert_eq!(remote_url(config, "origin"), None);
+        assert_eq!(
+            remote_url(config, "upstream").as_deref(),
+            Some("https://github.com/Nickiel12/itg.git")
+        );
+    }
+}
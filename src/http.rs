@@ -0,0 +1,224 @@
+use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use chrono::{DateTime, Local, TimeZone};
+use reqwest::{header::HeaderMap, Method, RequestBuilder, Response, StatusCode};
+use tokio::time::sleep;
+
+use crate::error::{Error, Result};
+
+/// How often a request is retried after a server error or a secondary rate limit.
+const MAX_RETRIES: u32 = 3;
+
+/// The backoff before the first retry, doubled for each further one.
+const BASE_DELAY: Duration = Duration::from_millis(500);
+
+/// Longest `Retry-After` of a secondary rate limit that is waited out, instead of failing. It
+/// is also the backoff of a secondary rate limit without `Retry-After`, as Github asks for.
+const MAX_RETRY_AFTER: Duration = Duration::from_secs(60);
+
+/// The primary rate limit of the token, as of the last response.
+#[derive(Clone, Copy)]
+pub struct RateLimit {
+    pub remaining: u32,
+    pub limit: u32,
+    pub reset: DateTime<Local>,
+}
+
+impl RateLimit {
+    fn from_headers(headers: &HeaderMap) -> Option<Self> {
+        let header = |name: &str| headers.get(name)?.to_str().ok();
+
+        Some(RateLimit {
+            remaining: header("x-ratelimit-remaining")?.parse().ok()?,
+            limit: header("x-ratelimit-limit")?.parse().ok()?,
+            reset: Local
+                .timestamp_opt(header("x-ratelimit-reset")?.parse().ok()?, 0)
+                .single()?,
+        })
+    }
+}
+
+/// The HTTP client all Github API requests go through. It keeps track of the rate limit and
+/// retries requests that failed for transient reasons.
+#[derive(Clone, Default)]
+pub struct GithubClient {
+    http: reqwest::Client,
+    rate_limit: Arc<Mutex<Option<RateLimit>>>,
+}
+
+impl GithubClient {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The underlying client, for requests outside of the API.
+    pub fn http(&self) -> &reqwest::Client {
+        &self.http
+    }
+
+    /// The rate limit of the core API as of the last response, if one was received yet.
+    pub fn rate_limit(&self) -> Option<RateLimit> {
+        *self.rate_limit.lock().unwrap()
+    }
+
+    /// Send `request` and turn an error status into an error. Server errors of all but POST
+    /// requests are retried with a jittered exponential backoff and secondary rate limits after
+    /// the time they ask for, an exhausted primary rate limit fails right away with its reset
+    /// time.
+    pub async fn send(&self, request: RequestBuilder) -> Result<Response> {
+        // A server error can arrive after a POST took effect, retrying it could create the
+        // issue or comment twice
+        let idempotent = request
+            .try_clone()
+            .and_then(|request| request.build().ok())
+            .is_some_and(|request| request.method() != Method::POST);
+        let mut attempt = 0;
+
+        loop {
+            // Only requests with a streamed body cannot be cloned, none are sent
+            let response = match request.try_clone() {
+                Some(request) => request.send().await?,
+                None => return Ok(request.send().await?.error_for_status()?),
+            };
+            self.track_rate_limit(response.headers());
+
+            let status = response.status();
+            let rate_limited =
+                status == StatusCode::FORBIDDEN || status == StatusCode::TOO_MANY_REQUESTS;
+            let header = |name: &str| response.headers().get(name)?.to_str().ok();
+
+            if rate_limited && header("x-ratelimit-remaining") == Some("0") {
+                if let Some(rate_limit) = RateLimit::from_headers(response.headers()) {
+                    return Err(Error::RateLimit {
+                        reset: rate_limit.reset,
+                    });
+                }
+            }
+
+            // Secondary rate limits tell how long to back off, or are only told apart by their
+            // message
+            let retry_after = match header("retry-after").and_then(|seconds| seconds.parse().ok()) {
+                Some(seconds) if rate_limited => Duration::from_secs(seconds),
+                _ if rate_limited => {
+                    let status_error = response.error_for_status_ref().err();
+                    let message = response.text().await.unwrap_or_default();
+
+                    match status_error {
+                        Some(err) if !message.contains("secondary rate limit") => {
+                            return Err(err.into())
+                        }
+                        _ => MAX_RETRY_AFTER,
+                    }
+                }
+                _ if idempotent && status.is_server_error() && attempt < MAX_RETRIES => {
+                    sleep(jitter(BASE_DELAY * 2u32.pow(attempt))).await;
+                    attempt += 1;
+                    continue;
+                }
+                _ => return Ok(response.error_for_status()?),
+            };
+
+            if retry_after > MAX_RETRY_AFTER || attempt == MAX_RETRIES {
+                return Err(Error::RateLimit {
+                    reset: Local::now()
+                        + chrono::Duration::from_std(retry_after).unwrap_or_default(),
+                });
+            }
+
+            sleep(retry_after + jitter(BASE_DELAY)).await;
+            attempt += 1;
+        }
+    }
+
+    fn track_rate_limit(&self, headers: &HeaderMap) {
+        // The search API has its own, much smaller limit
+        let resource = headers
+            .get("x-ratelimit-resource")
+            .and_then(|resource| resource.to_str().ok());
+
+        if resource.is_none_or(|resource| resource == "core") {
+            if let Some(rate_limit) = RateLimit::from_headers(headers) {
+                *self.rate_limit.lock().unwrap() = Some(rate_limit);
+            }
+        }
+    }
+}
+
+/// A random duration between half of `delay` and `delay`, so that retries spread out.
+fn jitter(delay: Duration) -> Duration {
+    delay / 2 + delay.mul_f64(fastrand::f64() / 2.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::mock_server::{serve, MockResponse};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn retries_server_errors_and_tracks_the_rate_limit() {
+        let url = serve(vec![
+            MockResponse::status("502 Bad Gateway"),
+            MockResponse::status("200 OK").headers(
+                "X-RateLimit-Remaining: 4990\r\nX-RateLimit-Limit: 5000\r\nX-RateLimit-Reset: 1700000000\r\n",
+            ),
+        ])
+        .await;
+        let client = GithubClient::new();
+
+        let response = client.send(client.http().get(&url)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let rate_limit = client.rate_limit().unwrap();
+        assert_eq!((rate_limit.remaining, rate_limit.limit), (4990, 5000));
+    }
+
+    #[tokio::test]
+    async fn fails_with_the_reset_time_when_the_rate_limit_is_used_up() {
+        let url = serve(vec![MockResponse::status("403 Forbidden").headers(
+            "X-RateLimit-Remaining: 0\r\nX-RateLimit-Limit: 5000\r\nX-RateLimit-Reset: 1700000000\r\n",
+        )])
+        .await;
+        let client = GithubClient::new();
+
+        match client.send(client.http().get(&url)).await {
+            Err(Error::RateLimit { reset }) => assert_eq!(reset.timestamp(), 1700000000),
+            _ => panic!("expected a rate limit error"),
+        }
+    }
+
+    #[tokio::test]
+    async fn fails_right_away_when_forbidden_without_a_rate_limit() {
+        // A retry would get the 200 and succeed
+        let url = serve(vec![
+            MockResponse::status("403 Forbidden"),
+            MockResponse::status("200 OK"),
+        ])
+        .await;
+        let client = GithubClient::new();
+
+        let result = client.send(client.http().get(&url)).await;
+        assert!(
+            matches!(result, Err(Error::Network(err)) if err.status() == Some(StatusCode::FORBIDDEN))
+        );
+    }
+
+    #[tokio::test]
+    async fn sends_a_failed_post_only_once() {
+        // A second request would get the 200 and succeed
+        let url = serve(vec![
+            MockResponse::status("502 Bad Gateway"),
+            MockResponse::status("200 OK"),
+        ])
+        .await;
+        let client = GithubClient::new();
+
+        let result = client.send(client.http().post(&url).body("{}")).await;
+        assert!(
+            matches!(result, Err(Error::Network(err)) if err.status() == Some(StatusCode::BAD_GATEWAY))
+        );
+    }
+}
@@ -1,16 +1,50 @@
+pub mod auth;
 pub mod controls;
+pub mod diff;
+pub mod editor;
+pub mod error;
+pub mod events;
+pub mod git;
+pub mod http;
+pub mod markdown;
+#[cfg(test)]
+mod mock_server;
 pub mod models;
+pub mod token;
 pub mod ui;
 
-use anyhow::Result;
+use anyhow::anyhow;
 use clap::Parser;
 use controls::run_app;
-use indicatif::{ProgressBar, ProgressStyle};
+use editor::{new_issue_draft_name, Draft};
+use error::{Error, Result};
+use http::GithubClient;
 use models::{
-    app_state::AppState, args::Args, config::Config, issue::Issue, menu_items::MenuItems,
+    app_state::AppState,
+    args::{Args, AuthCommand, Command, IssueCommand},
+    comment::Comment,
+    config::Config,
+    filters::{Direction, Filters, IssueSort},
+    issue::Issue,
+    label::Label,
+    menu_items::MenuItems,
+    milestone::Milestone,
+    new_issue::{NewIssue, NEW_ISSUE_TEMPLATE},
+    pull_request::PullRequest,
+    repository::Repository,
+    search_results::SearchResults,
+    user::User,
+};
+use reqwest::{
+    header::{HeaderMap, ACCEPT, AUTHORIZATION, LINK, USER_AGENT},
+    Method, RequestBuilder, Url,
+};
+use serde::{de::DeserializeOwned, Serialize};
+use std::{
+    env,
+    io::{self, Write},
+    sync::Arc,
 };
-use reqwest::header::{ACCEPT, AUTHORIZATION, USER_AGENT};
-use std::{io, time::Duration};
 
 use crossterm::{
     style::Stylize,
@@ -18,9 +52,19 @@ use crossterm::{
 };
 use tui::{backend::CrosstermBackend, Terminal};
 
-async fn fetch_issues(client: &reqwest::Client, config: &Config) -> Result<Vec<Issue>> {
-    Ok(client
-        .get("https://api.github.com/issues")
+fn github_get(client: &GithubClient, config: &Config, url: &str) -> RequestBuilder {
+    github_request(client, config, Method::GET, url)
+}
+
+fn github_request(
+    client: &GithubClient,
+    config: &Config,
+    method: Method,
+    url: &str,
+) -> RequestBuilder {
+    client
+        .http()
+        .request(method, url)
         .header(
             AUTHORIZATION,
             format!("Bearer {}", &config.github_access_token),
@@ -28,67 +72,623 @@ async fn fetch_issues(client: &reqwest::Client, config: &Config) -> Result<Vec<I
         .header(ACCEPT, "application/vnd.github+json")
         .header("X-GitHub-Api-Version", "2022-11-28")
         .header(USER_AGENT, &config.user_name)
-        .send()
+}
+
+/// Extract the `rel="next"` url from a Github `Link` header.
+fn next_page_url(headers: &HeaderMap) -> Option<String> {
+    headers
+        .get(LINK)?
+        .to_str()
+        .ok()?
+        .split(',')
+        .find_map(|link| {
+            let (url, params) = link.split_once(';')?;
+
+            if params
+                .split(';')
+                .any(|param| param.trim() == "rel=\"next\"")
+            {
+                Some(
+                    url.trim()
+                        .trim_start_matches('<')
+                        .trim_end_matches('>')
+                        .to_string(),
+                )
+            } else {
+                None
+            }
+        })
+}
+
+/// The items of a listing, minus the ones that could not be parsed.
+pub struct Fetched<T> {
+    pub items: Vec<T>,
+    /// A description of every item that was skipped
+    pub skipped: Vec<String>,
+}
+
+/// Parse `values` one by one into `fetched`, so a single malformed item is skipped instead of
+/// failing the whole listing.
+fn parse_items<T: DeserializeOwned>(values: Vec<serde_json::Value>, fetched: &mut Fetched<T>) {
+    for value in values {
+        let description = match value.get("html_url").and_then(|url| url.as_str()) {
+            Some(url) => url.to_string(),
+            None => String::from("unknown item"),
+        };
+
+        match serde_json::from_value::<T>(value) {
+            Ok(item) => fetched.items.push(item),
+            Err(err) => fetched.skipped.push(format!("{}: {}", description, err)),
+        }
+    }
+}
+
+/// Follow the `Link` headers from `url` and gather every page, stopping early once `limit`
+/// items have been fetched. `on_page` is called with the page number before each request.
+async fn fetch_all_pages<T: DeserializeOwned>(
+    client: &GithubClient,
+    config: &Config,
+    url: String,
+    limit: Option<usize>,
+    on_page: impl Fn(usize),
+) -> Result<Fetched<T>> {
+    let mut fetched = Fetched {
+        items: Vec::new(),
+        skipped: Vec::new(),
+    };
+    let mut next_url = Some(url);
+    let mut page = 1;
+
+    while let Some(url) = next_url {
+        on_page(page);
+
+        let response = client.send(github_get(client, config, &url)).await?;
+        next_url = next_page_url(response.headers());
+
+        parse_items(
+            response.json::<Vec<serde_json::Value>>().await?,
+            &mut fetched,
+        );
+
+        if let Some(limit) = limit {
+            if fetched.items.len() >= limit {
+                fetched.items.truncate(limit);
+                break;
+            }
+        }
+
+        page += 1;
+    }
+
+    Ok(fetched)
+}
+
+/// Fetch every page of issues, stopping early once `config.max_issues` is reached.
+pub async fn fetch_issues(
+    client: &GithubClient,
+    config: &Config,
+    filters: &Filters,
+    on_page: impl Fn(usize),
+) -> Result<Fetched<Issue>> {
+    let path = match &config.repository {
+        Some(repository) => format!("/repos/{}/issues", repository),
+        None => String::from("/issues"),
+    };
+
+    let mut params = filters.query_params(&config.user_name, config.repository.is_some())?;
+    params.push(("per_page", String::from("100")));
+
+    let mut fetched: Fetched<Issue> = fetch_all_pages(
+        client,
+        config,
+        Url::parse_with_params(&config.api_url(&path), &params)
+            .map_err(|err| Error::Parse(format!("Invalid url: {}", err)))?
+            .to_string(),
+        config.max_issues,
+        on_page,
+    )
+    .await?;
+
+    // Listing a single repository leaves the repository out of its issues
+    if let Some(repository) = &config.repository {
+        for issue in fetched.items.iter_mut() {
+            issue.repository.get_or_insert_with(|| Repository {
+                full_name: repository.clone(),
+            });
+        }
+    }
+
+    Ok(fetched)
+}
+
+/// A page of search results and the url of the next page.
+pub struct SearchPage {
+    pub fetched: Fetched<Issue>,
+    pub total_count: usize,
+    pub incomplete_results: bool,
+    pub next_url: Option<String>,
+}
+
+/// Number of search results fetched per page.
+pub const SEARCH_PAGE_SIZE: usize = 50;
+
+/// Fetch the first `pages` pages of the issues and pull requests matching the search `query` in
+/// the given order, or as many pages from `next_url` on to continue a search.
+pub async fn search_issues(
+    client: &GithubClient,
+    config: &Config,
+    query: &str,
+    sort: Option<IssueSort>,
+    direction: Option<Direction>,
+    next_url: Option<String>,
+    pages: usize,
+) -> Result<SearchPage> {
+    let mut url = match next_url {
+        Some(url) => url,
+        None => {
+            let mut params = vec![
+                ("q", query.to_string()),
+                ("per_page", SEARCH_PAGE_SIZE.to_string()),
+            ];
+            if let Some(sort) = sort {
+                params.push(("sort", sort.to_string()));
+            }
+            if let Some(direction) = direction {
+                params.push(("order", direction.to_string()));
+            }
+
+            Url::parse_with_params(&config.api_url("/search/issues"), &params)
+                .map_err(|err| Error::Parse(format!("Invalid url: {}", err)))?
+                .to_string()
+        }
+    };
+
+    let mut fetched: Fetched<Issue> = Fetched {
+        items: Vec::new(),
+        skipped: Vec::new(),
+    };
+    let mut page = 1;
+
+    let (total_count, incomplete_results, next_url) = loop {
+        let response = client.send(github_get(client, config, &url)).await?;
+        let next_url = next_page_url(response.headers());
+        let results = response.json::<SearchResults<serde_json::Value>>().await?;
+        parse_items(results.items, &mut fetched);
+
+        match next_url {
+            Some(next_url) if page < pages => {
+                url = next_url;
+                page += 1;
+            }
+            next_url => break (results.total_count, results.incomplete_results, next_url),
+        }
+    };
+
+    // Search results only link to their repository
+    for issue in fetched.items.iter_mut() {
+        if issue.repository.is_none() {
+            issue.repository = Some(Repository {
+                full_name: issue.repository_name(),
+            });
+        }
+    }
+
+    Ok(SearchPage {
+        fetched,
+        total_count,
+        incomplete_results,
+        next_url,
+    })
+}
+
+/// Fetch the user the access token belongs to.
+pub async fn fetch_user(client: &GithubClient, config: &Config) -> Result<User> {
+    Ok(client
+        .send(github_get(client, config, &config.api_url("/user")))
         .await?
-        .json::<Vec<Issue>>()
+        .json::<User>()
         .await?)
 }
 
-fn create_spinner(message: String) -> ProgressBar {
-    let spinner = ProgressBar::new_spinner();
-    spinner.enable_steady_tick(Duration::from_millis(120));
-    spinner.set_style(
-        ProgressStyle::with_template("{spinner} {msg}")
-            .unwrap()
-            .tick_strings(&["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏", "-"]),
+/// Fetch the whole comment thread of an issue.
+pub async fn fetch_comments(
+    client: &GithubClient,
+    config: &Config,
+    comments_url: &str,
+) -> Result<Vec<Comment>> {
+    Ok(fetch_all_pages(
+        client,
+        config,
+        format!("{}?per_page=100", comments_url),
+        None,
+        |_| {},
+    )
+    .await?
+    .items)
+}
+
+/// Fetch every label of `repository`, given as `owner/name`.
+pub async fn fetch_labels(
+    client: &GithubClient,
+    config: &Config,
+    repository: &str,
+) -> Result<Vec<Label>> {
+    Ok(fetch_all_pages(
+        client,
+        config,
+        config.api_url(&format!("/repos/{}/labels?per_page=100", repository)),
+        None,
+        |_| {},
+    )
+    .await?
+    .items)
+}
+
+/// Fetch the users that issues of `repository` can be assigned to.
+pub async fn fetch_assignees(
+    client: &GithubClient,
+    config: &Config,
+    repository: &str,
+) -> Result<Vec<User>> {
+    Ok(fetch_all_pages(
+        client,
+        config,
+        config.api_url(&format!("/repos/{}/assignees?per_page=100", repository)),
+        None,
+        |_| {},
+    )
+    .await?
+    .items)
+}
+
+/// Fetch the open milestones of `repository`, soonest due first.
+pub async fn fetch_milestones(
+    client: &GithubClient,
+    config: &Config,
+    repository: &str,
+) -> Result<Vec<Milestone>> {
+    Ok(fetch_all_pages(
+        client,
+        config,
+        config.api_url(&format!(
+            "/repos/{}/milestones?state=open&sort=due_on&per_page=100",
+            repository
+        )),
+        None,
+        |_| {},
+    )
+    .await?
+    .items)
+}
+
+/// Fetch the open pull requests the user authored, was requested to review or is assigned to.
+pub async fn fetch_pull_requests(
+    client: &GithubClient,
+    config: &Config,
+) -> Result<Vec<PullRequest>> {
+    let mut pull_requests: Vec<PullRequest> = Vec::new();
+
+    for qualifier in ["author", "review-requested", "assignee"] {
+        let mut query = format!("is:pr is:open {}:{}", qualifier, config.user_name);
+        if let Some(repository) = &config.repository {
+            query.push_str(&format!(" repo:{}", repository));
+        }
+
+        let mut next_url = Some(
+            Url::parse_with_params(
+                &config.api_url("/search/issues"),
+                &[("q", query.as_str()), ("per_page", "100")],
+            )
+            .map_err(|err| Error::Parse(format!("Invalid url: {}", err)))?
+            .to_string(),
+        );
+
+        while let Some(url) = next_url {
+            let response = client.send(github_get(client, config, &url)).await?;
+            next_url = next_page_url(response.headers());
+            let results = response.json::<SearchResults<PullRequest>>().await?;
+
+            // The same pull request can match several qualifiers
+            for pull_request in results.items {
+                if !pull_requests
+                    .iter()
+                    .any(|existing| existing.html_url == pull_request.html_url)
+                {
+                    pull_requests.push(pull_request);
+                }
+            }
+        }
+    }
+
+    Ok(pull_requests)
+}
+
+/// Create an issue in `repository`, given as `owner/name`.
+pub async fn create_issue(
+    client: &GithubClient,
+    config: &Config,
+    repository: &str,
+    new_issue: &NewIssue,
+) -> Result<Issue> {
+    let request = github_request(
+        client,
+        config,
+        Method::POST,
+        &config.api_url(&format!("/repos/{}/issues", repository)),
+    );
+    let mut issue = client
+        .send(request.json(new_issue))
+        .await?
+        .json::<Issue>()
+        .await?;
+
+    // The response lacks the repository, which the list shows and the issue actions need
+    issue.repository = Some(Repository {
+        full_name: repository.to_string(),
+    });
+
+    Ok(issue)
+}
+
+/// Post a comment on the issue whose comments live at `comments_url`.
+pub async fn create_comment(
+    client: &GithubClient,
+    config: &Config,
+    comments_url: &str,
+    body: &str,
+) -> Result<Comment> {
+    let request = github_request(client, config, Method::POST, comments_url)
+        .json(&serde_json::json!({ "body": body }));
+
+    Ok(client.send(request).await?.json::<Comment>().await?)
+}
+
+/// Fetch the issue at the API url `issue_url`.
+pub async fn fetch_issue(client: &GithubClient, config: &Config, issue_url: &str) -> Result<Issue> {
+    Ok(client
+        .send(github_get(client, config, issue_url))
+        .await?
+        .json::<Issue>()
+        .await?)
+}
+
+/// Apply `changes` to the issue at the API url `issue_url` and return the updated issue.
+pub async fn update_issue(
+    client: &GithubClient,
+    config: &Config,
+    issue_url: &str,
+    changes: &impl Serialize,
+) -> Result<Issue> {
+    let request = github_request(client, config, Method::PATCH, issue_url).json(changes);
+
+    Ok(client.send(request).await?.json::<Issue>().await?)
+}
+
+/// Replace the labels of the issue at the API url `issue_url`, returning the new labels.
+pub async fn set_issue_labels(
+    client: &GithubClient,
+    config: &Config,
+    issue_url: &str,
+    labels: &[String],
+) -> Result<Vec<Label>> {
+    let request = github_request(
+        client,
+        config,
+        Method::PUT,
+        &format!("{}/labels", issue_url),
+    )
+    .json(&serde_json::json!({ "labels": labels }));
+
+    Ok(client.send(request).await?.json::<Vec<Label>>().await?)
+}
+
+/// Lock the issue at the API url `issue_url` with an optional reason, or unlock it.
+pub async fn set_issue_lock(
+    client: &GithubClient,
+    config: &Config,
+    issue_url: &str,
+    locked: bool,
+    reason: Option<&str>,
+) -> Result<()> {
+    let url = format!("{}/lock", issue_url);
+
+    let request = if locked {
+        let body = match reason {
+            Some(reason) => serde_json::json!({ "lock_reason": reason }),
+            None => serde_json::json!({}),
+        };
+        github_request(client, config, Method::PUT, &url).json(&body)
+    } else {
+        github_request(client, config, Method::DELETE, &url)
+    };
+
+    client.send(request).await?;
+
+    Ok(())
+}
+
+/// Fill in a missing user name with the login of the token's user and store it in the profile.
+pub async fn fill_user_name(client: &GithubClient, config: &mut Config) -> Result<()> {
+    if config.user_name.is_empty() && !config.github_access_token.is_empty() {
+        if let Ok(user) = fetch_user(client, config).await {
+            config.update_stored(|stored| stored.user_name = user.login.clone())?;
+            config.user_name = user.login;
+        }
+    }
+
+    Ok(())
+}
+
+/// Run `itg auth login`: authorize through the OAuth device flow, then store the token and the
+/// login of its user.
+async fn login_command(
+    client: &GithubClient,
+    config: &mut Config,
+    client_id: Option<String>,
+) -> Result<()> {
+    let client_id = client_id.unwrap_or(config.oauth_client_id.clone());
+    if client_id.is_empty() {
+        return Err(Error::Auth(String::from(
+            "No OAuth app client id set. itg does not ship one, register an OAuth app with the \
+             device flow enabled and pass its client id with --client-id",
+        )));
+    }
+
+    let web_url = config.web_url();
+    let device_code = auth::request_device_code(client.http(), &web_url, &client_id).await?;
+
+    println!(
+        "Open {} and enter the code {}",
+        device_code.verification_uri, device_code.user_code
     );
-    spinner.set_message(message);
+    println!("Waiting for the authorization..");
 
-    spinner
+    config.github_access_token =
+        auth::poll_access_token(client.http(), &web_url, &client_id, &device_code).await?;
+    let user = fetch_user(client, config).await?;
+
+    config.update_stored(|stored| {
+        stored.github_access_token = config.github_access_token.clone();
+        stored.user_name = user.login.clone();
+        stored.oauth_client_id = client_id;
+    })?;
+
+    println!("Logged in as {}", user);
+    Ok(())
+}
+
+/// Run `itg issue create` outside of the TUI.
+async fn create_issue_command(
+    client: &GithubClient,
+    config: &Config,
+    repository: Option<String>,
+) -> anyhow::Result<()> {
+    let repository = match repository.or(config.repository.clone()) {
+        Some(repository) => repository,
+        None => {
+            print!("Repository (owner/name): ");
+            io::stdout().flush()?;
+
+            let mut repository = String::new();
+            io::stdin().read_line(&mut repository)?;
+            repository.trim().to_string()
+        }
+    };
+
+    let draft = Draft::open_or_create(&new_issue_draft_name(&repository), NEW_ISSUE_TEMPLATE)?;
+    let new_issue = NewIssue::parse(&draft.edit()?)
+        .map_err(|err| anyhow!("{} (the draft is kept at {})", err, draft.path.display()))?;
+
+    match create_issue(client, config, &repository, &new_issue).await {
+        Ok(issue) => {
+            draft.discard();
+            println!("Created {}", issue.html_url);
+            Ok(())
+        }
+        Err(err) => Err(anyhow!(
+            "{} (the draft is kept at {})",
+            err,
+            draft.path.display()
+        )),
+    }
 }
 
 #[tokio::main]
-async fn main() -> Result<()> {
-    let client = reqwest::Client::new();
-    let args = Args::parse();
+async fn main() {
+    // Errors that reach this far end the session, the TUI shows the recoverable ones itself
+    if let Err(err) = run(Args::parse()).await {
+        reset_terminal().unwrap_or_else(|_| panic!("Failed to reset terminal"));
+        eprintln!("{}: {}", "Error".red().bold(), err);
+        std::process::exit(1);
+    }
+}
 
-    let config = Config::initialise_config(Config {
-        github_access_token: args.token.unwrap_or(String::new()),
-        user_name: args.user_name.unwrap_or(String::new()),
-    });
+/// Everything below `run` reports the typed `Error`. Only `run` and `create_issue_command` use
+/// `anyhow`, so the latter can add where the draft is kept to whatever error it ended in.
+async fn run(args: Args) -> anyhow::Result<()> {
+    let client = GithubClient::new();
+
+    let mut config = Config::initialise_config(
+        args.profile.as_deref(),
+        Config {
+            github_access_token: args.token.clone().unwrap_or_default(),
+            user_name: args.user_name.unwrap_or_default(),
+            max_issues: args.max_issues,
+            api_base_url: args
+                .host
+                .map(|host| Config::api_base_url_from_host(&host))
+                .unwrap_or_default(),
+            ..Config::default()
+        },
+    )?;
+
+    if let Some(Command::Auth {
+        command: AuthCommand::Login { client_id },
+    }) = &args.command
+    {
+        return Ok(login_command(&client, &mut config, client_id.clone()).await?);
+    }
+
+    let resolved =
+        token::resolve_token(args.token, &config.web_host(), &config.github_access_token);
+
+    if args.show_token_source {
+        match &resolved {
+            Some((token, source)) => {
+                println!("Using the token from {}: {}", source, token::redact(token))
+            }
+            None => println!("No token found"),
+        }
+        return Ok(());
+    }
+
+    config.github_access_token = resolved.map(|(token, _)| token).unwrap_or_default();
+    fill_user_name(&client, &mut config).await?;
+
+    Config::check_empty_values(&config)?;
+
+    config.filters.override_with(args.filters);
+    config.repository = if args.all {
+        None
+    } else {
+        // A remote on another host than the profile's is unknown to its API
+        args.repo.or_else(|| {
+            git::detect_repository(&env::current_dir().ok()?)
+                .filter(|(host, _)| *host == config.web_host())
+                .map(|(_, repository)| repository)
+        })
+    };
 
     if args.file_path {
         eprintln!(
             "{:?}",
             confy::get_configuration_file_path("issue-tracker", None).unwrap()
         );
-        reset_terminal().unwrap_or_else(|_| panic!("Failed to reset terminal"));
-        std::process::exit(1);
+        return Ok(());
     }
 
-    let spinner = create_spinner(String::from("Fetching issues.."));
-    let issues = fetch_issues(&client, &config).await?;
-    spinner.finish();
+    if let Some(Command::Issue {
+        command: IssueCommand::Create { repo },
+    }) = args.command
+    {
+        return create_issue_command(&client, &config, repo).await;
+    }
 
     let mut terminal = init_terminal()?;
 
-    let app_state = AppState::new(issues);
-    let res = run_app(&mut terminal, app_state);
+    let app_state = AppState {
+        views: config.views.clone(),
+        ..AppState::default()
+    };
+    let res = run_app(&mut terminal, app_state, client, Arc::new(config)).await;
 
     reset_terminal()?;
 
-    if let Err(err) = res {
-        eprintln!("{}: {}", "Error".red().bold(), err);
-        reset_terminal().unwrap_or_else(|_| panic!("Failed to reset terminal"));
-        std::process::exit(1);
-    }
-
-    Ok(())
+    Ok(res?)
 }
 
 fn init_terminal() -> Result<Terminal<CrosstermBackend<io::Stdout>>> {
-    crossterm::execute!(io::stdout(), EnterAlternateScreen)?;
-    enable_raw_mode()?;
+    restore_terminal()?;
 
     let backend = CrosstermBackend::new(io::stdout());
 
@@ -105,9 +705,133 @@ fn init_terminal() -> Result<Terminal<CrosstermBackend<io::Stdout>>> {
     Ok(terminal)
 }
 
+/// Enter the alternate screen and raw mode, e.g. after the terminal was handed to an editor.
+fn restore_terminal() -> Result<()> {
+    crossterm::execute!(io::stdout(), EnterAlternateScreen)?;
+    enable_raw_mode()?;
+
+    Ok(())
+}
+
 fn reset_terminal() -> Result<()> {
     disable_raw_mode()?;
     crossterm::execute!(io::stdout(), LeaveAlternateScreen)?;
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use crate::mock_server::{serve, MockResponse};
+
+    use super::*;
+
+    const ISSUE: &str = r#"[{
+        "url": "https://github.example.com/api/v3/repos/octocat/hello/issues/7",
+        "html_url": "https://github.example.com/octocat/hello/issues/7",
+        "comments_url": "https://github.example.com/api/v3/repos/octocat/hello/issues/7/comments",
+        "number": 7,
+        "title": "Enterprise issue",
+        "state": "open",
+        "user": { "login": "octocat" },
+        "created_at": "2024-03-01T12:00:00Z",
+        "updated_at": "2024-03-02T12:00:00Z"
+    }]"#;
+
+    fn repository_names(fetched: &Fetched<Repository>) -> Vec<&str> {
+        fetched
+            .items
+            .iter()
+            .map(|repository| repository.full_name.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn parse_items_skips_malformed_items() {
+        let mut fetched: Fetched<Repository> = Fetched {
+            items: Vec::new(),
+            skipped: Vec::new(),
+        };
+
+        parse_items(
+            vec![
+                json!({ "full_name": "octocat/hello" }),
+                json!({ "full_name": 7, "html_url": "https://github.com/octocat/broken" }),
+                json!({ "full_name": "octocat/world" }),
+            ],
+            &mut fetched,
+        );
+
+        assert_eq!(
+            repository_names(&fetched),
+            ["octocat/hello", "octocat/world"]
+        );
+        assert_eq!(fetched.skipped.len(), 1);
+        assert!(fetched.skipped[0].starts_with("https://github.com/octocat/broken: invalid type"));
+    }
+
+    #[tokio::test]
+    async fn fetch_all_pages_follows_the_next_links() {
+        let url = serve(vec![
+            MockResponse::json("/repos", r#"[{"full_name":"a/1"},{"full_name":"a/2"}]"#).headers(
+                "Link: <{url}/repos?page=2>; rel=\"next\", <{url}/repos?page=2>; rel=\"last\"\r\n",
+            ),
+            MockResponse::json("/repos", r#"[{"full_name":"a/3"}]"#),
+        ])
+        .await;
+        let client = GithubClient::new();
+
+        let fetched: Fetched<Repository> = fetch_all_pages(
+            &client,
+            &Config::default(),
+            format!("{}/repos", url),
+            None,
+            |_| {},
+        )
+        .await
+        .unwrap();
+        assert_eq!(repository_names(&fetched), ["a/1", "a/2", "a/3"]);
+    }
+
+    #[tokio::test]
+    async fn fetch_all_pages_stops_at_the_limit() {
+        // Only the first page is served, a request for the second would fail
+        let url = serve(vec![MockResponse::json(
+            "/repos",
+            r#"[{"full_name":"a/1"},{"full_name":"a/2"}]"#,
+        )
+        .headers("Link: <{url}/repos?page=2>; rel=\"next\"\r\n")])
+        .await;
+        let client = GithubClient::new();
+
+        let fetched: Fetched<Repository> = fetch_all_pages(
+            &client,
+            &Config::default(),
+            format!("{}/repos", url),
+            Some(1),
+            |_| {},
+        )
+        .await
+        .unwrap();
+        assert_eq!(repository_names(&fetched), ["a/1"]);
+    }
+
+    #[tokio::test]
+    async fn fetch_issues_uses_the_api_base_url() {
+        let url = serve(vec![MockResponse::json("/api/v3/issues", ISSUE)]).await;
+        let client = GithubClient::new();
+        let config = Config {
+            api_base_url: format!("{}/api/v3", url),
+            user_name: String::from("octocat"),
+            ..Config::default()
+        };
+
+        let fetched = fetch_issues(&client, &config, &Filters::default(), |_| {})
+            .await
+            .unwrap();
+        assert_eq!(fetched.items.len(), 1);
+        assert_eq!(fetched.items[0].repository_name(), "octocat/hello");
+    }
+}
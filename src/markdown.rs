@@ -0,0 +1,568 @@
+use pulldown_cmark::{CodeBlockKind, Event, HeadingLevel, Options, Parser, Tag, TagEnd};
+use tui::{
+    style::{Color, Modifier, Style},
+    text::{Span, Spans, Text},
+};
+
+/// Render Github flavored markdown to styled terminal text.
+pub fn markdown_to_text(content: &str) -> Text<'static> {
+    let options =
+        Options::ENABLE_TABLES | Options::ENABLE_TASKLISTS | Options::ENABLE_STRIKETHROUGH;
+
+    let mut renderer = Renderer::default();
+    for event in Parser::new_ext(content, options) {
+        renderer.handle(event);
+    }
+
+    renderer.finish()
+}
+
+/// A table being collected, rendered once all of its cells are known.
+#[derive(Default)]
+struct Table {
+    rows: Vec<Vec<String>>,
+    row: Vec<String>,
+    cell: String,
+}
+
+/// A link being rendered, its url is appended after the text unless they are the same.
+struct Link {
+    url: String,
+    text: String,
+}
+
+#[derive(Default)]
+struct Renderer {
+    lines: Vec<Spans<'static>>,
+    line: Vec<Span<'static>>,
+    styles: Vec<Style>,
+    /// The next number of every open list, `None` for bullet lists
+    lists: Vec<Option<u64>>,
+    /// Marker of a list item that has not been written yet
+    item_marker: Option<String>,
+    quote_depth: usize,
+    code_block: bool,
+    html_comment: bool,
+    links: Vec<Link>,
+    table: Option<Table>,
+}
+
+impl Renderer {
+    fn handle(&mut self, event: Event) {
+        match event {
+            Event::Start(tag) => self.start(tag),
+            Event::End(tag) => self.end(tag),
+            Event::Text(text) => self.text(&text),
+            Event::Code(code) => {
+                if let Some(table) = self.table.as_mut() {
+                    table.cell.push_str(&code);
+                } else {
+                    self.push(code.to_string(), self.style().fg(Color::Yellow));
+                }
+            }
+            Event::Html(html) | Event::InlineHtml(html) => self.html(&html),
+            Event::SoftBreak => self.text(" "),
+            Event::HardBreak => self.flush(),
+            Event::Rule => {
+                self.start_block();
+                self.push(
+                    String::from("────────────────"),
+                    Style::default().fg(Color::DarkGray),
+                );
+                self.flush();
+            }
+            Event::TaskListMarker(checked) => {
+                self.item_marker = Some(String::from(if checked { "☑ " } else { "☐ " }));
+            }
+            _ => {}
+        }
+    }
+
+    fn start(&mut self, tag: Tag) {
+        match tag {
+            // The first paragraph of a list item starts on the marker's line
+            Tag::Paragraph if self.item_marker.is_none() => self.start_block(),
+            Tag::Heading { level, .. } => {
+                self.start_block();
+
+                let (prefix, style) = match level {
+                    HeadingLevel::H1 => ("██ ", Style::default().fg(Color::Green)),
+                    HeadingLevel::H2 => ("▓▓▓ ", Style::default().fg(Color::Cyan)),
+                    HeadingLevel::H3 => ("▒▒▒▒ ", Style::default().fg(Color::Yellow)),
+                    HeadingLevel::H4 => ("░░░░░ ", Style::default().fg(Color::Magenta)),
+                    _ => ("", Style::default()),
+                };
+                let style = style.add_modifier(Modifier::BOLD);
+
+                self.styles.push(style);
+                self.push(String::from(prefix), style);
+            }
+            Tag::BlockQuote(_) => {
+                self.start_block();
+                self.quote_depth += 1;
+            }
+            Tag::CodeBlock(kind) => {
+                self.start_block();
+                self.code_block = true;
+
+                if let CodeBlockKind::Fenced(language) = kind {
+                    if !language.is_empty() {
+                        self.push(language.to_string(), Style::default().fg(Color::DarkGray));
+                        self.flush();
+                    }
+                }
+            }
+            Tag::List(start) => {
+                if self.lists.is_empty() {
+                    self.start_block();
+                } else {
+                    self.flush();
+                }
+
+                self.lists.push(start);
+            }
+            Tag::Item => {
+                self.flush();
+
+                self.item_marker = match self.lists.last_mut() {
+                    Some(Some(number)) => {
+                        let marker = format!("{}. ", number);
+                        *number += 1;
+                        Some(marker)
+                    }
+                    _ => Some(String::from("• ")),
+                };
+            }
+            Tag::Table(_) => {
+                self.start_block();
+                self.table = Some(Table::default());
+            }
+            Tag::Emphasis => self
+                .styles
+                .push(self.style().add_modifier(Modifier::ITALIC)),
+            Tag::Strong => self.styles.push(self.style().add_modifier(Modifier::BOLD)),
+            Tag::Strikethrough => self
+                .styles
+                .push(self.style().add_modifier(Modifier::CROSSED_OUT)),
+            Tag::Link { dest_url, .. } => {
+                self.styles.push(
+                    self.style()
+                        .fg(Color::Blue)
+                        .add_modifier(Modifier::UNDERLINED),
+                );
+                self.links.push(Link {
+                    url: dest_url.to_string(),
+                    text: String::new(),
+                });
+            }
+            Tag::Image { dest_url, .. } => {
+                self.push(
+                    String::from("[image: "),
+                    Style::default().fg(Color::DarkGray),
+                );
+                self.styles.push(Style::default().fg(Color::DarkGray));
+                self.links.push(Link {
+                    url: dest_url.to_string(),
+                    text: String::new(),
+                });
+            }
+            _ => {}
+        }
+    }
+
+    fn end(&mut self, tag: TagEnd) {
+        match tag {
+            TagEnd::Paragraph => self.flush(),
+            TagEnd::Heading(_) => {
+                self.styles.pop();
+                self.flush();
+            }
+            TagEnd::BlockQuote(_) => {
+                self.flush();
+                self.quote_depth = self.quote_depth.saturating_sub(1);
+            }
+            TagEnd::CodeBlock => {
+                self.flush();
+                self.code_block = false;
+            }
+            TagEnd::List(_) => {
+                self.flush();
+                self.lists.pop();
+            }
+            TagEnd::Item => self.flush(),
+            TagEnd::Table => {
+                if let Some(table) = self.table.take() {
+                    self.table(table);
+                }
+            }
+            TagEnd::TableHead | TagEnd::TableRow => {
+                if let Some(table) = self.table.as_mut() {
+                    let row = std::mem::take(&mut table.row);
+                    table.rows.push(row);
+                }
+            }
+            TagEnd::TableCell => {
+                if let Some(table) = self.table.as_mut() {
+                    let cell = std::mem::take(&mut table.cell);
+                    table.row.push(cell.trim().to_string());
+                }
+            }
+            TagEnd::Emphasis | TagEnd::Strong | TagEnd::Strikethrough => {
+                self.styles.pop();
+            }
+            TagEnd::Link => {
+                self.styles.pop();
+
+                if let Some(link) = self.links.pop() {
+                    if link.text != link.url {
+                        self.push(
+                            format!(" ({})", link.url),
+                            Style::default().fg(Color::DarkGray),
+                        );
+                    }
+                }
+            }
+            TagEnd::Image => {
+                self.styles.pop();
+                self.links.pop();
+                self.push(String::from("]"), Style::default().fg(Color::DarkGray));
+            }
+            _ => {}
+        }
+    }
+
+    fn text(&mut self, text: &str) {
+        if let Some(table) = self.table.as_mut() {
+            table.cell.push_str(text);
+            return;
+        }
+
+        if self.code_block {
+            for line in text.lines() {
+                self.push(line.to_string(), Style::default().fg(Color::Yellow));
+                self.flush();
+            }
+            return;
+        }
+
+        if let Some(link) = self.links.last_mut() {
+            link.text.push_str(text);
+            self.push(text.to_string(), self.style());
+            return;
+        }
+
+        // Highlight bare urls, Github turns them into links
+        let mut rest = text;
+        while let Some(start) = find_url(rest) {
+            let end = rest[start..]
+                .find(char::is_whitespace)
+                .map_or(rest.len(), |end| start + end);
+            // Trailing punctuation is not part of the url
+            let end = start
+                + rest[start..end]
+                    .trim_end_matches(['.', ',', ';', ':', '!', '?', ')'])
+                    .len();
+
+            self.push(rest[..start].to_string(), self.style());
+            self.push(
+                rest[start..end].to_string(),
+                self.style()
+                    .fg(Color::Blue)
+                    .add_modifier(Modifier::UNDERLINED),
+            );
+            rest = &rest[end..];
+        }
+        self.push(rest.to_string(), self.style());
+    }
+
+    fn html(&mut self, html: &str) {
+        let mut rest = html;
+
+        loop {
+            if self.html_comment {
+                match rest.find("-->") {
+                    Some(end) => {
+                        self.html_comment = false;
+                        rest = &rest[end + 3..];
+                    }
+                    None => return,
+                }
+            } else {
+                match rest.find("<!--") {
+                    Some(start) => {
+                        self.push_html(&rest[..start]);
+                        self.html_comment = true;
+                        rest = &rest[start + 4..];
+                    }
+                    None => {
+                        self.push_html(rest);
+                        return;
+                    }
+                }
+            }
+        }
+    }
+
+    /// Write html that is not part of a comment as dimmed raw text.
+    fn push_html(&mut self, html: &str) {
+        let mut lines = html.split('\n').peekable();
+
+        while let Some(line) = lines.next() {
+            if !line.trim().is_empty() {
+                self.push(line.to_string(), Style::default().fg(Color::DarkGray));
+            }
+
+            if lines.peek().is_some() {
+                self.flush();
+            }
+        }
+    }
+
+    fn table(&mut self, table: Table) {
+        let columns = table.rows.iter().map(|row| row.len()).max().unwrap_or(0);
+        let widths: Vec<usize> = (0..columns)
+            .map(|column| {
+                table
+                    .rows
+                    .iter()
+                    .filter_map(|row| row.get(column))
+                    .map(|cell| cell.chars().count())
+                    .max()
+                    .unwrap_or(0)
+            })
+            .collect();
+
+        for (index, row) in table.rows.iter().enumerate() {
+            let cells: Vec<String> = widths
+                .iter()
+                .enumerate()
+                .map(|(column, width)| {
+                    format!(
+                        "{:width$}",
+                        row.get(column).map_or("", |cell| cell.as_str()),
+                        width = width
+                    )
+                })
+                .collect();
+
+            let style = if index == 0 {
+                Style::default().add_modifier(Modifier::BOLD)
+            } else {
+                Style::default()
+            };
+            self.push(cells.join(" │ "), style);
+            self.flush();
+
+            // Separate the header from the body
+            if index == 0 {
+                let separator: Vec<String> =
+                    widths.iter().map(|width| "─".repeat(*width)).collect();
+                self.push(separator.join("─┼─"), Style::default().fg(Color::DarkGray));
+                self.flush();
+            }
+        }
+    }
+
+    /// The style of the innermost open inline element.
+    fn style(&self) -> Style {
+        self.styles.last().copied().unwrap_or_default()
+    }
+
+    fn push(&mut self, content: String, style: Style) {
+        if !content.is_empty() {
+            self.line.push(Span::styled(content, style));
+        }
+    }
+
+    /// Separate a new block from the previous one with an empty line.
+    fn start_block(&mut self) {
+        self.flush();
+
+        if self
+            .lines
+            .last()
+            .is_some_and(|line| line.width() > self.prefix(false).width())
+        {
+            let prefix = self.prefix(false);
+            self.lines.push(prefix);
+        }
+    }
+
+    /// The block quote bars and list indentation starting every line.
+    fn prefix(&self, first_line_of_item: bool) -> Spans<'static> {
+        let mut prefix = vec![];
+
+        if self.quote_depth > 0 {
+            prefix.push(Span::styled(
+                "│ ".repeat(self.quote_depth),
+                Style::default().fg(Color::DarkGray),
+            ));
+        }
+
+        if !self.lists.is_empty() {
+            let indent = "  ".repeat(self.lists.len() - 1);
+
+            match (&self.item_marker, first_line_of_item) {
+                (Some(marker), true) => {
+                    prefix.push(Span::raw(indent));
+                    prefix.push(Span::styled(
+                        marker.clone(),
+                        Style::default().fg(Color::Cyan),
+                    ));
+                }
+                _ => prefix.push(Span::raw(format!("{}  ", indent))),
+            }
+        }
+
+        Spans::from(prefix)
+    }
+
+    /// Finish the current line.
+    fn flush(&mut self) {
+        if self.line.is_empty() && self.item_marker.is_none() {
+            return;
+        }
+
+        let mut line = self.prefix(true);
+        line.0.append(&mut self.line);
+        self.lines.push(line);
+        self.item_marker = None;
+    }
+
+    fn finish(mut self) -> Text<'static> {
+        self.flush();
+
+        Text::from(self.lines)
+    }
+}
+
+/// Find the start of the first http(s) url in `text`.
+fn find_url(text: &str) -> Option<usize> {
+    match (text.find("https://"), text.find("http://")) {
+        (Some(https), Some(http)) => Some(https.min(http)),
+        (https, http) => https.or(http),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The plain text of every rendered line.
+    fn lines(content: &str) -> Vec<String> {
+        markdown_to_text(content)
+            .lines
+            .iter()
+            .map(|line| {
+                line.0
+                    .iter()
+                    .map(|span| span.content.as_ref())
+                    .collect::<String>()
+            })
+            .collect()
+    }
+
+    /// The style of the span containing `needle`.
+    fn style_of(content: &str, needle: &str) -> Style {
+        markdown_to_text(content)
+            .lines
+            .iter()
+            .flat_map(|line| line.0.iter())
+            .find(|span| span.content.contains(needle))
+            .map(|span| span.style)
+            .unwrap_or_else(|| panic!("{:?} was not rendered", needle))
+    }
+
+    #[test]
+    fn renders_headings() {
+        assert_eq!(lines("# One\n## Two"), vec!["██ One", "", "▓▓▓ Two"]);
+        assert!(style_of("# One", "One")
+            .add_modifier
+            .contains(Modifier::BOLD));
+    }
+
+    #[test]
+    fn hashtags_are_not_headings() {
+        assert_eq!(lines("#hashtag"), vec!["#hashtag"]);
+    }
+
+    #[test]
+    fn renders_fenced_code_verbatim() {
+        assert_eq!(
+            lines("```sh\n# not a heading\n  indented\n```"),
+            vec!["sh", "# not a heading", "  indented"]
+        );
+        assert_eq!(
+            style_of("```\n# comment\n```", "# comment").fg,
+            Some(Color::Yellow)
+        );
+    }
+
+    #[test]
+    fn renders_task_lists() {
+        assert_eq!(lines("- [x] done\n- [ ] todo"), vec!["☑ done", "☐ todo"]);
+    }
+
+    #[test]
+    fn renders_tables() {
+        assert_eq!(
+            lines("| a | long header |\n|---|---|\n| value | b |"),
+            vec![
+                "a     │ long header",
+                "──────┼────────────",
+                "value │ b          "
+            ]
+        );
+    }
+
+    #[test]
+    fn renders_block_quotes() {
+        assert_eq!(
+            lines("> quoted\n>\n> > nested"),
+            vec!["│ quoted", "│ ", "│ │ nested"]
+        );
+    }
+
+    #[test]
+    fn renders_nested_lists() {
+        assert_eq!(
+            lines("1. first\n   - inner\n   - other\n2. second"),
+            vec!["1. first", "  • inner", "  • other", "2. second"]
+        );
+    }
+
+    #[test]
+    fn renders_strikethrough() {
+        assert_eq!(lines("~~gone~~"), vec!["gone"]);
+        assert!(style_of("~~gone~~", "gone")
+            .add_modifier
+            .contains(Modifier::CROSSED_OUT));
+    }
+
+    #[test]
+    fn renders_autolinks() {
+        assert_eq!(lines("<https://github.com>"), vec!["https://github.com"]);
+        assert_eq!(
+            lines("see https://github.com/foo."),
+            vec!["see https://github.com/foo."]
+        );
+        assert!(
+            style_of("see https://github.com/foo.", "https://github.com/foo")
+                .add_modifier
+                .contains(Modifier::UNDERLINED)
+        );
+        assert_eq!(
+            lines("[docs](https://docs.rs)"),
+            vec!["docs (https://docs.rs)"]
+        );
+    }
+
+    #[test]
+    fn hides_html_comments() {
+        assert_eq!(
+            lines("<!-- Describe the bug\nin detail -->\nThe bug"),
+            vec!["The bug"]
+        );
+        assert_eq!(lines("before <!-- hidden --> after"), vec!["before  after"]);
+    }
+}
@@ -0,0 +1,105 @@
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::TcpListener,
+};
+
+/// A canned response of the mock server.
+pub struct MockResponse {
+    /// Path the request is expected at, without the query, any path if unset
+    path: Option<&'static str>,
+    /// Status line, e.g. `200 OK`
+    status: &'static str,
+    /// Extra header lines, each ending in `\r\n`. `{url}` stands for the server's base url,
+    /// e.g. to link to the next page
+    headers: &'static str,
+    body: &'static str,
+}
+
+impl MockResponse {
+    /// An empty JSON object with the status line `status`, for a request at any path.
+    pub fn status(status: &'static str) -> Self {
+        MockResponse {
+            path: None,
+            status,
+            headers: "",
+            body: "{}",
+        }
+    }
+
+    /// A successful response with the JSON `body`, for a request at `path`.
+    pub fn json(path: &'static str, body: &'static str) -> Self {
+        MockResponse {
+            path: Some(path),
+            status: "200 OK",
+            headers: "",
+            body,
+        }
+    }
+
+    pub fn headers(self, headers: &'static str) -> Self {
+        MockResponse { headers, ..self }
+    }
+}
+
+/// Serve the `responses` in order on a local port, one request each, and return its base url.
+pub async fn serve(responses: Vec<MockResponse>) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    let base_url = url.clone();
+
+    tokio::spawn(async move {
+        for response in responses {
+            let (mut stream, _) = listener.accept().await.unwrap();
+
+            // Read the whole request, its headers and then its body
+            let mut request = String::new();
+            let mut buffer = [0; 1024];
+            loop {
+                let read = stream.read(&mut buffer).await.unwrap();
+                request.push_str(&String::from_utf8_lossy(&buffer[..read]));
+
+                if let Some((headers, body)) = request.split_once("\r\n\r\n") {
+                    let length = headers
+                        .lines()
+                        .find_map(|line| {
+                            line.to_lowercase()
+                                .strip_prefix("content-length:")
+                                .map(|length| length.trim().parse::<usize>().unwrap())
+                        })
+                        .unwrap_or(0);
+
+                    if body.len() >= length {
+                        break;
+                    }
+                }
+
+                if read == 0 {
+                    break;
+                }
+            }
+
+            if let Some(path) = response.path {
+                assert_eq!(
+                    request
+                        .split_whitespace()
+                        .nth(1)
+                        .and_then(|target| target.split('?').next()),
+                    Some(path),
+                    "unexpected request {}",
+                    request
+                );
+            }
+
+            let answer = format!(
+                "HTTP/1.1 {}\r\n{}Content-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                response.status,
+                response.headers.replace("{url}", &base_url),
+                response.body.len(),
+                response.body
+            );
+            stream.write_all(answer.as_bytes()).await.unwrap();
+        }
+    });
+
+    url
+}
@@ -1,19 +1,176 @@
-use crate::Issue;
+use std::collections::HashMap;
 
-use super::{menu_items::MenuItems, stateful_list::StatefulList};
+use crate::{events::Task, http::RateLimit, Issue};
+
+use super::{config::Config, filters::Filters, saved_view::SavedView};
+
+use super::{
+    confirmation::Confirmation, conflict::Conflict, detail_view::DetailView, focus::Focus,
+    label::Label, menu_items::MenuItems, milestone::Milestone, picker::IssuePicker, prompt::Prompt,
+    pull_request::PullRequest, search_query::SearchQuery, stateful_list::StatefulList, user::User,
+};
 
 pub struct AppState {
     pub current_menu: MenuItems,
     pub issues: StatefulList<Issue>,
+    /// Issues that could not be parsed and were left out of `issues`
+    pub skipped_issues: Vec<String>,
+    pub pull_requests: StatefulList<PullRequest>,
+    /// Set while the detail view of the selected issue is open
+    pub detail_view: Option<DetailView>,
+    /// Saved views shown as tabs after the menu items
+    pub views: Vec<SavedView>,
+    /// Index into `views` of the view shown in the issue list, the user's issues if unset
+    pub current_view: Option<usize>,
+    /// Counts how often the issue list was cleared, so that the issues still being fetched for
+    /// what it showed before can be told apart
+    pub issues_generation: u64,
+    /// Github search whose results are shown instead of the user's issues
+    pub search_query: Option<SearchQuery>,
+    /// Set while the search prompt is receiving key presses
+    pub searching: bool,
+    /// Popup text input receiving key presses
+    pub prompt: Option<Prompt>,
+    /// Modal dialog waiting for an issue action to be confirmed
+    pub confirmation: Option<Confirmation>,
+    /// Edit that was not saved because the issue changed on the server
+    pub conflict: Option<Conflict>,
+    /// Popup choosing the labels, assignees or milestone of the selected issue
+    pub picker: Option<IssuePicker>,
+    /// Labels of every repository fetched so far, keyed by `owner/name`
+    pub labels: HashMap<String, Vec<Label>>,
+    /// Assignable users of every repository fetched so far, keyed by `owner/name`
+    pub assignees: HashMap<String, Vec<User>>,
+    /// Open milestones of every repository fetched so far, keyed by `owner/name`
+    pub milestones: HashMap<String, Vec<Milestone>>,
+    pub focus: Focus,
+    /// Preview scroll offset per item, keyed by the item's html url
+    pub preview_scroll: HashMap<String, u16>,
+    /// Height of the preview pane's content, updated on every draw
+    pub preview_height: u16,
+    /// Network tasks in flight and their progress message
+    pub tasks: HashMap<Task, String>,
+    /// Frame of the status bar spinner, advanced on every tick
+    pub spinner_frame: usize,
+    /// The last error of a background task
+    pub error: Option<String>,
+    /// The rate limit of the token as of the last response, copied from the client on every draw
+    pub rate_limit: Option<RateLimit>,
 }
 
 impl AppState {
-    pub fn new(issues: Vec<Issue>) -> Self {
-        Self {
-            current_menu: MenuItems::Issues,
-            issues: StatefulList::with_items(issues),
+    /// Move the selection of the current tab's list forward.
+    pub fn next(&mut self) {
+        match self.current_menu {
+            MenuItems::Issues => self.issues.next(),
+            MenuItems::PullRequests => self.pull_requests.next(),
+        }
+    }
+
+    /// Move the selection of the current tab's list backwards.
+    pub fn previous(&mut self) {
+        match self.current_menu {
+            MenuItems::Issues => self.issues.previous(),
+            MenuItems::PullRequests => self.pull_requests.previous(),
+        }
+    }
+
+    /// The html url of the selected item in the current tab's list.
+    pub fn selected_html_url(&self) -> Option<String> {
+        match self.current_menu {
+            MenuItems::Issues => self
+                .issues
+                .selected_item()
+                .map(|issue| issue.html_url.clone()),
+            MenuItems::PullRequests => self
+                .pull_requests
+                .selected_item()
+                .map(|pull_request| pull_request.html_url.clone()),
+        }
+    }
+
+    /// The preview scroll offset of the selected item.
+    pub fn preview_scroll(&self) -> u16 {
+        self.selected_html_url()
+            .and_then(|html_url| self.preview_scroll.get(&html_url).copied())
+            .unwrap_or(0)
+    }
+
+    /// Set the preview scroll offset of the selected item. The ui clamps it to the content.
+    pub fn set_preview_scroll(&mut self, scroll: u16) {
+        if let Some(html_url) = self.selected_html_url() {
+            self.preview_scroll.insert(html_url, scroll);
+        }
+    }
+
+    /// Scroll the preview of the selected item by `lines`, negative values scroll up.
+    pub fn scroll_preview(&mut self, lines: i32) {
+        let scroll = (self.preview_scroll() as i32 + lines).clamp(0, u16::MAX as i32);
+        self.set_preview_scroll(scroll as u16);
+    }
+
+    /// Half the height of the preview pane, used for page scrolling.
+    pub fn half_page(&self) -> i32 {
+        (self.preview_height / 2).max(1) as i32
+    }
+
+    /// The issue shown in the detail view.
+    pub fn detail_issue(&self) -> Option<&Issue> {
+        let detail_view = self.detail_view.as_ref()?;
+
+        self.issues
+            .items
+            .iter()
+            .find(|issue| issue.url == detail_view.issue_url)
+    }
+
+    /// The issue the issue actions apply to: the one in the detail view, or the selected one.
+    pub fn current_issue(&self) -> Option<&Issue> {
+        match self.detail_view {
+            Some(_) => self.detail_issue(),
+            None => self.issues.selected_item(),
+        }
+    }
+
+    /// Close the detail view once its issue was dropped from the list, e.g. by a refresh.
+    pub fn close_stale_detail_view(&mut self) {
+        if self.detail_view.is_some() && self.detail_issue().is_none() {
+            self.detail_view = None;
+        }
+    }
+
+    /// Replace the issue with the same url by `issue`, in place.
+    pub fn update_issue(&mut self, mut issue: Issue) {
+        if let Some(existing) = self
+            .issues
+            .items
+            .iter_mut()
+            .find(|existing| existing.url == issue.url)
+        {
+            // Responses about a single issue lack the repository, keep the one already known
+            if issue.repository.is_none() {
+                issue.repository = existing.repository.take();
+            }
+
+            *existing = issue;
+            self.issues.apply_query();
         }
     }
+
+    /// The filters of the current saved view, or the configured ones.
+    pub fn active_filters<'a>(&'a self, config: &'a Config) -> &'a Filters {
+        self.current_view
+            .and_then(|index| self.views.get(index))
+            .map_or(&config.filters, |view| &view.filters)
+    }
+
+    /// Switch the focus between the list and the preview.
+    pub fn toggle_focus(&mut self) {
+        self.focus = match self.focus {
+            Focus::List => Focus::Preview,
+            Focus::Preview => Focus::List,
+        };
+    }
 }
 
 impl Default for AppState {
@@ -21,6 +178,28 @@ impl Default for AppState {
         Self {
             current_menu: MenuItems::Issues,
             issues: StatefulList::with_items(vec![]),
+            skipped_issues: Vec::new(),
+            pull_requests: StatefulList::with_items(vec![]),
+            detail_view: None,
+            views: Vec::new(),
+            current_view: None,
+            issues_generation: 0,
+            search_query: None,
+            searching: false,
+            prompt: None,
+            confirmation: None,
+            conflict: None,
+            picker: None,
+            labels: HashMap::new(),
+            assignees: HashMap::new(),
+            milestones: HashMap::new(),
+            focus: Focus::List,
+            preview_scroll: HashMap::new(),
+            preview_height: 0,
+            tasks: HashMap::new(),
+            spinner_frame: 0,
+            error: None,
+            rate_limit: None,
         }
     }
 }
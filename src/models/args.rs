@@ -1,17 +1,89 @@
-use clap::Parser;
+use clap::{Parser, Subcommand};
+
+use super::filters::Filters;
 
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
 pub struct Args {
-    /// Github access token
+    /// Profile of the config file to use, defaults to its default_profile. An unknown profile
+    /// is created if --token, --user-name or --host give it values
+    #[arg(long)]
+    pub profile: Option<String>,
+
+    /// Github access token, defaults to GITHUB_TOKEN or GH_TOKEN (GITHUB_ENTERPRISE_TOKEN or
+    /// GH_ENTERPRISE_TOKEN on other hosts than github.com), the gh CLI's login, git's credential
+    /// helpers and then the config file
     #[arg(short, long)]
     pub token: Option<String>,
 
-    /// Github user name
+    /// Print where the access token was found and exit
+    #[arg(long)]
+    pub show_token_source: bool,
+
+    /// Github user name, defaults to the login of the token's user
     #[arg(short, long)]
     pub user_name: Option<String>,
 
+    /// Github host or API base url, e.g. github.example.com for Github Enterprise Server. It is
+    /// saved to the profile, so later runs keep using it
+    #[arg(long)]
+    pub host: Option<String>,
+
+    /// Maximum number of issues to fetch in this run, defaults to max_issues of the config file
+    #[arg(short, long)]
+    pub max_issues: Option<usize>,
+
+    /// Repository to list the issues and pull requests of, as owner/name. Defaults to the
+    /// repository of the git remote in the working directory
+    #[arg(short, long, conflicts_with = "all")]
+    pub repo: Option<String>,
+
+    /// List the issues and pull requests of all repositories, even inside a git checkout
+    #[arg(short, long)]
+    pub all: bool,
+
+    #[command(flatten)]
+    pub filters: Filters,
+
     /// Print the config file path
     #[clap(short, long, action)]
     pub file_path: bool,
+
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    /// Work with issues
+    Issue {
+        #[command(subcommand)]
+        command: IssueCommand,
+    },
+    /// Manage the access token
+    Auth {
+        #[command(subcommand)]
+        command: AuthCommand,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum IssueCommand {
+    /// Write a new issue in $EDITOR and create it
+    Create {
+        /// Repository to create the issue in, as owner/name
+        #[arg(short, long)]
+        repo: Option<String>,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum AuthCommand {
+    /// Log in through the browser and store the access token
+    Login {
+        /// Client id of an OAuth app with the device flow enabled. Required on the first login,
+        /// it is stored for later ones
+        #[arg(long)]
+        client_id: Option<String>,
+    },
 }
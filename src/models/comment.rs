@@ -0,0 +1,13 @@
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+
+use super::user::User;
+
+#[derive(Deserialize)]
+pub struct Comment {
+    pub html_url: String,
+    pub user: User,
+    pub body: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
@@ -1,26 +1,130 @@
-use crossterm::style::Stylize;
+use std::collections::BTreeMap;
+
+use reqwest::Url;
 use serde::{Deserialize, Serialize};
 
-use crate::reset_terminal;
+use crate::error::{Error, Result};
+
+use super::{filters::Filters, saved_view::SavedView};
+
+pub const DEFAULT_API_BASE_URL: &str = "https://api.github.com";
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(default)]
 pub struct Config {
     pub github_access_token: String,
     pub user_name: String,
+    pub max_issues: Option<usize>,
+    pub api_base_url: String,
+    /// Seconds between background refreshes of the lists
+    pub refresh_interval: u64,
+    /// Client id of the OAuth app `itg auth login` authorizes, it needs the device flow enabled
+    pub oauth_client_id: String,
+    /// Default filters of the issue list, overridden by the command line flags. Kept last, as
+    /// tables have to follow the plain values in the config file
+    pub filters: Filters,
+    /// Named filters and search queries shown as tabs. An empty list would be written as a
+    /// plain value after the filters table, which is invalid
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub views: Vec<SavedView>,
+    /// The `owner/name` the lists are scoped to, from `--repo` or the git remote. Lists
+    /// everything of the user if unset.
+    #[serde(skip)]
+    pub repository: Option<String>,
+    /// Name of the profile this config was loaded from
+    #[serde(skip)]
+    pub profile: String,
+}
+
+/// Name of the profile used without `--profile` or `default_profile`.
+pub const DEFAULT_PROFILE: &str = "default";
+
+/// The config file: named profiles, e.g. a github.com account and a Github Enterprise one.
+#[derive(Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct ConfigFile {
+    /// Profile used without `--profile`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default_profile: Option<String>,
+    pub profiles: BTreeMap<String, Config>,
+}
+
+impl ConfigFile {
+    pub fn load() -> Result<Self> {
+        let mut file: ConfigFile = confy::load("issue-tracker", None)?;
+
+        // A config file written before profiles holds the settings of the default profile
+        if file.profiles.is_empty() {
+            let config: Config = confy::load("issue-tracker", None)?;
+            file.profiles.insert(String::from(DEFAULT_PROFILE), config);
+        }
+
+        Ok(file)
+    }
+
+    pub fn store(&self) -> Result<()> {
+        confy::store("issue-tracker", None, self)?;
+        Ok(())
+    }
+
+    /// The name of `profile`, or of the default profile if unset.
+    pub fn profile_name(&self, profile: Option<&str>) -> String {
+        profile
+            .map(str::to_string)
+            .or(self.default_profile.clone())
+            .unwrap_or(String::from(DEFAULT_PROFILE))
+    }
 }
 
 impl Config {
-    pub fn initialise_config(new_config: Config) -> Config {
-        let mut config: Config = confy::load("issue-tracker", None).unwrap_or_else(|err| {
-            eprintln!("{}: {}", "Error".red().bold(), err);
-            reset_terminal().unwrap_or_else(|_| panic!("Failed to reset terminal"));
-            std::process::exit(1);
-        });
+    /// Load `profile`, or the default profile, and store the token, user name and host given on
+    /// the command line in `new_config` to it. An unknown profile is only created if
+    /// `new_config` sets one of them, so that a mistyped name is not stored.
+    pub fn initialise_config(profile: Option<&str>, new_config: Config) -> Result<Config> {
+        let mut file = ConfigFile::load()?;
+        let name = file.profile_name(profile);
+
+        let has_values = !new_config.github_access_token.is_empty()
+            || !new_config.user_name.is_empty()
+            || !new_config.api_base_url.is_empty();
+        if !has_values && !file.profiles.contains_key(&name) {
+            return Err(Error::Config(format!(
+                "There is no profile named {}. Create it by passing --token, --user-name or \
+                 --host along with --profile",
+                name
+            )));
+        }
 
-        Config::load_new_config(&mut config, new_config);
-        Config::check_empty_values(&config);
+        let max_issues = new_config.max_issues;
+        let config = file.profiles.entry(name.clone()).or_default();
+        Config::load_new_config(config, new_config);
+        let mut config = config.clone();
+        config.profile = name;
 
-        config
+        // A limit on the command line is for this run only
+        if max_issues.is_some() {
+            config.max_issues = max_issues;
+        }
+
+        file.store()?;
+
+        Ok(config)
+    }
+
+    /// Load the stored profile `name`.
+    pub fn load_profile(name: &str) -> Result<Config> {
+        let mut config = ConfigFile::load()?
+            .profiles
+            .remove(name)
+            .ok_or(Error::Config(format!("There is no profile named {}", name)))?;
+        config.profile = name.to_string();
+
+        Ok(config)
+    }
+
+    /// The names of all stored profiles, in order.
+    pub fn profile_names() -> Result<Vec<String>> {
+        Ok(ConfigFile::load()?.profiles.into_keys().collect())
     }
 
     fn load_new_config(config: &mut Config, new_config: Config) {
@@ -34,39 +138,104 @@ impl Config {
             config.user_name = new_config.user_name;
         }
 
-        confy::store("issue-tracker", None, &config).unwrap_or_else(|err| {
-            eprintln!("{}: {}", "Error".red().bold(), err);
-            reset_terminal().unwrap_or_else(|_| panic!("Failed to reset terminal"));
-            std::process::exit(1);
-        });
+        if !new_config.api_base_url.is_empty() && new_config.api_base_url != config.api_base_url {
+            config.api_base_url = new_config.api_base_url;
+        }
     }
 
-    fn check_empty_values(config: &Config) {
+    pub fn check_empty_values(config: &Config) -> Result<()> {
         if config.github_access_token.is_empty() {
-            eprintln!(
-                "{}: No Github access token set. Please set one with the --token (-t) flag.",
-                "Error".red().bold()
-            );
-            reset_terminal().unwrap_or_else(|_| panic!("Failed to reset terminal"));
-            std::process::exit(1);
+            return Err(Error::Auth(String::from(
+                "No Github access token found. Please log in with `itg auth login`, set one with \
+                 the --token (-t) flag or GITHUB_TOKEN (GITHUB_ENTERPRISE_TOKEN or \
+                 GH_ENTERPRISE_TOKEN on other hosts than github.com), or log in with the gh CLI.",
+            )));
         }
 
         if config.user_name.is_empty() {
-            eprintln!(
-                "{}: No Github user name is set. Please set one with the --user-name (-u) flag.",
-                "Error".red().bold()
-            );
-            reset_terminal().unwrap_or_else(|_| panic!("Failed to reset terminal"));
-            std::process::exit(1);
+            return Err(Error::Config(String::from(
+                "No Github user name is set and it could not be fetched with the token. Please \
+                 set one with the --user-name (-u) flag.",
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Change the stored profile of this config with `update`, leaving out the values of this
+    /// run's flags.
+    pub fn update_stored(&self, update: impl FnOnce(&mut Config)) -> Result<()> {
+        let mut file = ConfigFile::load()?;
+        update(file.profiles.entry(self.profile.clone()).or_default());
+
+        file.store()
+    }
+
+    /// Add `view` to the stored profile, replacing the view of the same name.
+    pub fn save_view(&self, view: SavedView) -> Result<()> {
+        self.update_stored(|config| {
+            config.views.retain(|existing| existing.name != view.name);
+            config.views.push(view);
+        })
+    }
+
+    /// Build the full url of an API endpoint from the configured base url.
+    pub fn api_url(&self, path: &str) -> String {
+        format!("{}{}", self.api_base_url.trim_end_matches('/'), path)
+    }
+
+    /// The host of the Github web ui the API belongs to, e.g. `github.com`.
+    pub fn web_host(&self) -> String {
+        let host = Url::parse(&self.api_base_url)
+            .ok()
+            .and_then(|url| url.host_str().map(str::to_string))
+            .unwrap_or_default();
+
+        match host.strip_prefix("api.") {
+            Some(host) => host.to_string(),
+            None => host,
+        }
+    }
+
+    /// The base url of the Github web ui the API belongs to, e.g. `https://github.com`.
+    pub fn web_url(&self) -> String {
+        match Url::parse(&self.api_base_url) {
+            Ok(url) => match url.port() {
+                Some(port) => format!("{}://{}:{}", url.scheme(), self.web_host(), port),
+                None => format!("{}://{}", url.scheme(), self.web_host()),
+            },
+            Err(_) => String::from("https://github.com"),
+        }
+    }
+
+    /// Turn a `--host` value into an API base url.
+    ///
+    /// Full urls are used as is, `github.com` maps to the public API and any other bare host is
+    /// treated as a Github Enterprise Server instance.
+    pub fn api_base_url_from_host(host: &str) -> String {
+        if host.starts_with("http://") || host.starts_with("https://") {
+            host.to_string()
+        } else if host == "github.com" || host == "api.github.com" {
+            String::from(DEFAULT_API_BASE_URL)
+        } else {
+            format!("https://{}/api/v3", host.trim_end_matches('/'))
         }
     }
 }
 
-impl ::std::default::Default for Config {
+impl Default for Config {
     fn default() -> Self {
         Self {
             github_access_token: String::from(""),
             user_name: String::from(""),
+            max_issues: None,
+            api_base_url: String::from(DEFAULT_API_BASE_URL),
+            refresh_interval: 300,
+            oauth_client_id: String::new(),
+            filters: Filters::default(),
+            views: Vec::new(),
+            repository: None,
+            profile: String::from(DEFAULT_PROFILE),
         }
     }
 }
@@ -0,0 +1,59 @@
+use core::fmt;
+
+/// Reasons Github accepts for locking an issue.
+pub const LOCK_REASONS: [&str; 4] = ["off-topic", "too heated", "resolved", "spam"];
+
+/// A change to an issue's state that has to be confirmed first.
+pub enum IssueAction {
+    CloseAsCompleted,
+    CloseAsNotPlanned,
+    Reopen,
+    /// Lock with the reason at this index of `LOCK_REASONS`, or without a reason
+    Lock(Option<usize>),
+    Unlock,
+}
+
+impl fmt::Display for IssueAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::CloseAsCompleted => write!(f, "Close as completed"),
+            Self::CloseAsNotPlanned => write!(f, "Close as not planned"),
+            Self::Reopen => write!(f, "Reopen"),
+            Self::Lock(_) => write!(f, "Lock"),
+            Self::Unlock => write!(f, "Unlock"),
+        }
+    }
+}
+
+/// The modal dialog asking to confirm an `IssueAction`.
+pub struct Confirmation {
+    pub action: IssueAction,
+    /// API url of the issue
+    pub issue_url: String,
+    /// `#number title` of the issue, shown in the dialog
+    pub issue_name: String,
+}
+
+impl Confirmation {
+    /// Cycle through the lock reasons, starting from no reason.
+    pub fn next_lock_reason(&mut self) {
+        if let IssueAction::Lock(reason) = &mut self.action {
+            *reason = match reason {
+                None => Some(0),
+                Some(index) if *index + 1 < LOCK_REASONS.len() => Some(*index + 1),
+                Some(_) => None,
+            };
+        }
+    }
+
+    /// Cycle backwards through the lock reasons.
+    pub fn previous_lock_reason(&mut self) {
+        if let IssueAction::Lock(reason) = &mut self.action {
+            *reason = match reason {
+                None => Some(LOCK_REASONS.len() - 1),
+                Some(0) => None,
+                Some(index) => Some(*index - 1),
+            };
+        }
+    }
+}
@@ -0,0 +1,20 @@
+use chrono::{DateTime, Utc};
+
+use crate::{diff::DiffLine, editor::Draft};
+
+use super::issue_edit::IssueEdit;
+
+/// An edit that was not sent because the issue changed on the server since it was opened.
+pub struct Conflict {
+    pub issue_url: String,
+    pub issue_name: String,
+    pub edit: IssueEdit,
+    /// The draft holding the edit, reopened when editing again
+    pub draft: Draft,
+    /// When the issue on the server was last updated, the new base of the draft once it is
+    /// edited again
+    pub updated_at: DateTime<Utc>,
+    /// The issue on the server diffed against the edit
+    pub diff: Vec<DiffLine>,
+    pub scroll: u16,
+}
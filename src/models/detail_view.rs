@@ -0,0 +1,36 @@
+use super::comment::Comment;
+
+/// State of the full screen detail view of an issue.
+pub struct DetailView {
+    /// API url of the issue shown, which stays open when the list is refreshed or reordered
+    pub issue_url: String,
+    pub comments_url: String,
+    pub comments: Vec<Comment>,
+    /// Set until the comment thread has been fetched
+    pub loading: bool,
+    pub scroll: u16,
+}
+
+impl DetailView {
+    /// Open the detail view of the issue at `issue_url` while its comments are fetched from
+    /// `comments_url`.
+    pub fn new(issue_url: String, comments_url: String) -> Self {
+        Self {
+            issue_url,
+            comments_url,
+            comments: Vec::new(),
+            loading: true,
+            scroll: 0,
+        }
+    }
+
+    /// Scroll the view down by one line.
+    pub fn scroll_down(&mut self) {
+        self.scroll = self.scroll.saturating_add(1);
+    }
+
+    /// Scroll the view up by one line.
+    pub fn scroll_up(&mut self) {
+        self.scroll = self.scroll.saturating_sub(1);
+    }
+}
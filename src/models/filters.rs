@@ -0,0 +1,284 @@
+use chrono::{DateTime, NaiveDate, NaiveTime, SecondsFormat, Utc};
+use clap::ValueEnum;
+use core::fmt;
+use serde::{Deserialize, Serialize};
+
+use crate::error::{self, Error};
+
+/// Which of the user's issues to list.
+#[derive(Serialize, Deserialize, ValueEnum, Clone, Copy, PartialEq, Debug)]
+#[serde(rename_all = "lowercase")]
+pub enum IssueFilter {
+    Assigned,
+    Created,
+    Mentioned,
+    Subscribed,
+    All,
+}
+
+#[derive(Serialize, Deserialize, ValueEnum, Clone, Copy, PartialEq, Debug)]
+#[serde(rename_all = "lowercase")]
+pub enum IssueState {
+    Open,
+    Closed,
+    All,
+}
+
+#[derive(Serialize, Deserialize, ValueEnum, Clone, Copy, PartialEq, Debug)]
+#[serde(rename_all = "lowercase")]
+pub enum IssueSort {
+    Created,
+    Updated,
+    Comments,
+}
+
+#[derive(Serialize, Deserialize, ValueEnum, Clone, Copy, PartialEq, Debug)]
+#[serde(rename_all = "lowercase")]
+pub enum Direction {
+    Asc,
+    Desc,
+}
+
+// Server side filters of the issue list. Unset filters fall back to the API's defaults. A
+// plain comment, as clap would take a doc comment as the description of the whole app
+#[derive(Serialize, Deserialize, clap::Args, Clone, Default, Debug)]
+#[serde(default)]
+pub struct Filters {
+    /// Which issues to list. Defaults to assigned across all repositories and to every issue of
+    /// a single repository, where subscribed and all are not available
+    #[arg(long, value_enum)]
+    pub filter: Option<IssueFilter>,
+
+    /// State of the issues to list, defaults to open
+    #[arg(long, value_enum)]
+    pub state: Option<IssueState>,
+
+    /// Only list issues with all of these labels, comma separated
+    #[arg(long, value_delimiter = ',')]
+    pub labels: Vec<String>,
+
+    /// What to sort the issues by, defaults to created
+    #[arg(long, value_enum)]
+    pub sort: Option<IssueSort>,
+
+    /// Sort direction, defaults to desc
+    #[arg(long, value_enum)]
+    pub direction: Option<Direction>,
+
+    /// Only list issues updated since, as YYYY-MM-DD or an RFC 3339 timestamp
+    #[arg(long, value_parser = parse_since)]
+    pub since: Option<DateTime<Utc>>,
+}
+
+impl Filters {
+    /// Replace the filters that are set in `overrides`.
+    pub fn override_with(&mut self, overrides: Filters) {
+        if overrides.filter.is_some() {
+            self.filter = overrides.filter;
+        }
+
+        if overrides.state.is_some() {
+            self.state = overrides.state;
+        }
+
+        if !overrides.labels.is_empty() {
+            self.labels = overrides.labels;
+        }
+
+        if overrides.sort.is_some() {
+            self.sort = overrides.sort;
+        }
+
+        if overrides.direction.is_some() {
+            self.direction = overrides.direction;
+        }
+
+        if overrides.since.is_some() {
+            self.since = overrides.since;
+        }
+    }
+
+    /// The query parameters of the issues endpoint.
+    ///
+    /// Listing a single repository has no `filter` parameter, so `user_name` is passed as the
+    /// assignee, creator or mentioned user instead. `subscribed` and `all` have no equivalent
+    /// there and are rejected.
+    pub fn query_params(
+        &self,
+        user_name: &str,
+        single_repository: bool,
+    ) -> error::Result<Vec<(&str, String)>> {
+        let mut params = Vec::new();
+
+        match (self.filter, single_repository) {
+            (Some(filter), false) => params.push(("filter", filter.to_string())),
+            (Some(IssueFilter::Assigned), true) => params.push(("assignee", user_name.to_string())),
+            (Some(IssueFilter::Created), true) => params.push(("creator", user_name.to_string())),
+            (Some(IssueFilter::Mentioned), true) => {
+                params.push(("mentioned", user_name.to_string()))
+            }
+            (Some(filter), true) => {
+                return Err(Error::Config(format!(
+                    "The filter {} only applies across all repositories, list them with --all",
+                    filter
+                )))
+            }
+            (None, _) => {}
+        }
+
+        if let Some(state) = self.state {
+            params.push(("state", state.to_string()));
+        }
+
+        if !self.labels.is_empty() {
+            params.push(("labels", self.labels.join(",")));
+        }
+
+        if let Some(sort) = self.sort {
+            params.push(("sort", sort.to_string()));
+        }
+
+        if let Some(direction) = self.direction {
+            params.push(("direction", direction.to_string()));
+        }
+
+        if let Some(since) = self.since {
+            params.push(("since", since.to_rfc3339_opts(SecondsFormat::Secs, true)));
+        }
+
+        Ok(params)
+    }
+}
+
+/// The active filters as `key:value` pairs, empty if none are set.
+impl fmt::Display for Filters {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut parts = Vec::new();
+
+        if let Some(filter) = self.filter {
+            parts.push(format!("filter:{}", filter));
+        }
+
+        if let Some(state) = self.state {
+            parts.push(format!("state:{}", state));
+        }
+
+        if !self.labels.is_empty() {
+            parts.push(format!("labels:{}", self.labels.join(",")));
+        }
+
+        if let Some(sort) = self.sort {
+            parts.push(format!("sort:{}", sort));
+        }
+
+        if let Some(direction) = self.direction {
+            parts.push(format!("direction:{}", direction));
+        }
+
+        if let Some(since) = self.since {
+            parts.push(format!("since:{}", since.format("%Y-%m-%d")));
+        }
+
+        write!(f, "{}", parts.join(" "))
+    }
+}
+
+/// The name a value is given on the command line and in the query parameters.
+fn value_name(value: &impl ValueEnum) -> String {
+    value
+        .to_possible_value()
+        .map(|value| value.get_name().to_string())
+        .unwrap_or_default()
+}
+
+impl fmt::Display for IssueFilter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", value_name(self))
+    }
+}
+
+impl fmt::Display for IssueState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", value_name(self))
+    }
+}
+
+impl fmt::Display for IssueSort {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", value_name(self))
+    }
+}
+
+impl fmt::Display for Direction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", value_name(self))
+    }
+}
+
+/// Parse a `--since` value, a date meaning its start in UTC.
+fn parse_since(value: &str) -> Result<DateTime<Utc>, String> {
+    if let Ok(date) = NaiveDate::parse_from_str(value, "%Y-%m-%d") {
+        return Ok(date.and_time(NaiveTime::MIN).and_utc());
+    }
+
+    DateTime::parse_from_rfc3339(value)
+        .map(|since| since.with_timezone(&Utc))
+        .map_err(|err| format!("expected YYYY-MM-DD or an RFC 3339 timestamp: {}", err))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn filters() -> Filters {
+        Filters {
+            filter: Some(IssueFilter::Created),
+            state: Some(IssueState::All),
+            labels: vec![String::from("bug"), String::from("ui")],
+            sort: None,
+            direction: Some(Direction::Asc),
+            since: Some(parse_since("2024-03-01").unwrap()),
+        }
+    }
+
+    #[test]
+    fn query_params_of_the_user_wide_listing() {
+        assert_eq!(
+            filters().query_params("octocat", false).unwrap(),
+            vec![
+                ("filter", String::from("created")),
+                ("state", String::from("all")),
+                ("labels", String::from("bug,ui")),
+                ("direction", String::from("asc")),
+                ("since", String::from("2024-03-01T00:00:00Z")),
+            ]
+        );
+    }
+
+    #[test]
+    fn repository_listing_filters_by_user() {
+        assert_eq!(
+            filters().query_params("octocat", true).unwrap()[0],
+            ("creator", String::from("octocat"))
+        );
+
+        let subscribed = Filters {
+            filter: Some(IssueFilter::Subscribed),
+            ..Filters::default()
+        };
+        assert!(subscribed.query_params("octocat", true).is_err());
+    }
+
+    #[test]
+    fn overrides_replace_set_filters_only() {
+        let mut filters = filters();
+        filters.override_with(Filters {
+            state: Some(IssueState::Closed),
+            ..Filters::default()
+        });
+
+        assert_eq!(filters.state, Some(IssueState::Closed));
+        assert_eq!(filters.filter, Some(IssueFilter::Created));
+        assert_eq!(filters.labels, vec!["bug", "ui"]);
+    }
+}
@@ -0,0 +1,6 @@
+/// The pane receiving the navigation keys.
+#[derive(PartialEq)]
+pub enum Focus {
+    List,
+    Preview,
+}
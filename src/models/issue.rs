@@ -1,16 +1,181 @@
+use chrono::{DateTime, Utc};
 use core::fmt;
-use serde::Deserialize;
+use serde::{Deserialize, Deserializer};
+
+use super::{
+    label::Label, milestone::Milestone, repository::Repository, searchable::Searchable, user::User,
+};
+
+/// Marker Github sets on issues that are actually pull requests.
+#[derive(Deserialize)]
+pub struct PullRequestMarker {
+    pub html_url: String,
+}
+
+/// Deserialize a missing or `null` value as the type's default.
+fn null_as_default<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Default + Deserialize<'de>,
+{
+    Ok(Option::<T>::deserialize(deserializer)?.unwrap_or_default())
+}
 
 #[derive(Deserialize)]
 pub struct Issue {
+    /// API url of the issue
+    pub url: String,
     pub html_url: String,
+    pub comments_url: String,
     pub number: usize,
     pub title: String,
-    pub body: String,
+    /// `None` when the issue was opened without a description
+    #[serde(default)]
+    pub body: Option<String>,
+    pub state: String,
+    /// `completed`, `not_planned` or `reopened`
+    #[serde(default)]
+    pub state_reason: Option<String>,
+    #[serde(default)]
+    pub locked: bool,
+    #[serde(default)]
+    pub active_lock_reason: Option<String>,
+    pub user: User,
+    #[serde(default, deserialize_with = "null_as_default")]
+    pub labels: Vec<Label>,
+    #[serde(default, deserialize_with = "null_as_default")]
+    pub assignees: Vec<User>,
+    #[serde(default)]
+    pub milestone: Option<Milestone>,
+    #[serde(default, deserialize_with = "null_as_default")]
+    pub comments: usize,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    #[serde(default)]
+    pub repository: Option<Repository>,
+    #[serde(default)]
+    pub pull_request: Option<PullRequestMarker>,
+}
+
+impl Issue {
+    pub fn is_pull_request(&self) -> bool {
+        self.pull_request.is_some()
+    }
+
+    /// The `owner/name` of the issue's repository, taken from its API url when the listing did
+    /// not include the repository.
+    pub fn repository_name(&self) -> String {
+        match &self.repository {
+            Some(repository) => repository.full_name.clone(),
+            None => self
+                .url
+                .split("/repos/")
+                .nth(1)
+                .and_then(|path| path.rsplitn(3, '/').nth(2))
+                .unwrap_or_default()
+                .to_string(),
+        }
+    }
 }
 
 impl fmt::Display for Issue {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}: {}", self.number, self.title)
+        if let Some(repository) = &self.repository {
+            write!(f, "{}", repository.full_name)?;
+        }
+
+        write!(f, "#{}: ", self.number)?;
+
+        if self.is_pull_request() {
+            write!(f, "[PR] ")?;
+        }
+
+        write!(f, "{}", self.title)?;
+
+        if self.state != "open" {
+            write!(f, " ({})", self.state)?;
+        }
+
+        if self.locked {
+            write!(f, " (locked)")?;
+        }
+
+        if !self.labels.is_empty() {
+            let labels: Vec<&str> = self
+                .labels
+                .iter()
+                .map(|label| label.name.as_str())
+                .collect();
+            write!(f, " [{}]", labels.join(", "))?;
+        }
+
+        if !self.assignees.is_empty() {
+            let assignees: Vec<String> =
+                self.assignees.iter().map(|user| user.to_string()).collect();
+            write!(f, " → {}", assignees.join(", "))?;
+        }
+
+        if let Some(milestone) = &self.milestone {
+            write!(f, " ⚑ {}", milestone.title)?;
+        }
+
+        if self.comments > 0 {
+            write!(f, " ({} comments)", self.comments)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Searchable for Issue {
+    fn search_text(&self) -> String {
+        let labels: Vec<&str> = self
+            .labels
+            .iter()
+            .map(|label| label.name.as_str())
+            .collect();
+
+        format!(
+            "#{} {} {} {}",
+            self.number,
+            self.title,
+            labels.join(" "),
+            self.repository
+                .as_ref()
+                .map_or("", |repository| repository.full_name.as_str())
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_null_and_missing_fields() {
+        let issue: Issue = serde_json::from_str(
+            r#"{
+                "url": "https://api.github.com/repos/octocat/hello/issues/7",
+                "html_url": "https://github.com/octocat/hello/issues/7",
+                "comments_url": "https://api.github.com/repos/octocat/hello/issues/7/comments",
+                "number": 7,
+                "title": "Empty issue",
+                "body": null,
+                "state": "open",
+                "user": { "login": "octocat" },
+                "labels": null,
+                "comments": null,
+                "created_at": "2024-03-01T12:00:00Z",
+                "updated_at": "2024-03-02T12:00:00Z"
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(issue.body, None);
+        assert!(issue.labels.is_empty() && issue.assignees.is_empty());
+        assert_eq!(issue.comments, 0);
+        assert!(issue.milestone.is_none() && issue.state_reason.is_none());
+        assert!(!issue.locked && !issue.is_pull_request());
+        assert_eq!(issue.repository_name(), "octocat/hello");
     }
 }
@@ -0,0 +1,122 @@
+use crate::error::{Error, Result};
+use serde::Serialize;
+
+use super::issue::Issue;
+
+/// The changes made to an issue's title and body, as sent to the issue endpoint.
+#[derive(Serialize)]
+pub struct IssueEdit {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub body: Option<String>,
+}
+
+impl IssueEdit {
+    /// The text opened in the editor to edit `issue`: front matter with the title, followed by
+    /// the markdown body.
+    pub fn template(issue: &Issue) -> String {
+        format!(
+            "---\ntitle: {}\n---\n\n{}\n",
+            issue.title,
+            issue.body.as_deref().unwrap_or_default().trim()
+        )
+    }
+
+    /// Parse an edited `IssueEdit::template`, keeping only what differs from `issue`.
+    pub fn parse(content: &str, issue: &Issue) -> Result<Self> {
+        let mut lines = content.lines();
+        if lines.next().map(|line| line.trim()) != Some("---") {
+            return Err(Error::Parse(String::from(
+                "The issue has to start with the --- front matter",
+            )));
+        }
+
+        let mut title = String::new();
+        for line in lines.by_ref() {
+            if line.trim() == "---" {
+                break;
+            }
+
+            match line.split_once(':') {
+                Some((key, value)) if key.trim() == "title" => title = value.trim().to_string(),
+                Some((key, _)) => {
+                    return Err(Error::Parse(format!(
+                        "Unknown front matter key {:?}",
+                        key.trim()
+                    )))
+                }
+                None => continue,
+            }
+        }
+
+        if title.is_empty() {
+            return Err(Error::Parse(String::from(
+                "No title given, the issue was not edited",
+            )));
+        }
+
+        let body = lines.collect::<Vec<&str>>().join("\n").trim().to_string();
+
+        Ok(Self {
+            title: (title != issue.title).then_some(title),
+            body: (body != issue.body.as_deref().unwrap_or_default().trim()).then_some(body),
+        })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.title.is_none() && self.body.is_none()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn issue() -> Issue {
+        serde_json::from_str(
+            r#"{
+                "url": "https://api.github.com/repos/octocat/hello/issues/7",
+                "html_url": "https://github.com/octocat/hello/issues/7",
+                "comments_url": "https://api.github.com/repos/octocat/hello/issues/7/comments",
+                "number": 7,
+                "title": "Crash on start",
+                "body": "It crashes.",
+                "state": "open",
+                "user": { "login": "octocat" },
+                "created_at": "2024-03-01T12:00:00Z",
+                "updated_at": "2024-03-02T12:00:00Z"
+            }"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn keeps_only_the_changed_fields() {
+        let issue = issue();
+
+        let unchanged = IssueEdit::parse(&IssueEdit::template(&issue), &issue).unwrap();
+        assert!(unchanged.title.is_none() && unchanged.body.is_none());
+        assert!(unchanged.is_empty());
+
+        let edit = IssueEdit::parse(
+            "---\ntitle: Crash on start\n---\n\nIt crashes.\n\n---\n\nLog below\n",
+            &issue,
+        )
+        .unwrap();
+        assert_eq!(edit.title, None);
+        assert_eq!(
+            edit.body.as_deref(),
+            Some("It crashes.\n\n---\n\nLog below")
+        );
+    }
+
+    #[test]
+    fn rejects_malformed_front_matter() {
+        let issue = issue();
+
+        assert!(IssueEdit::parse("title: No front matter\n", &issue).is_err());
+        assert!(IssueEdit::parse("---\ntitel: Typo\n---\n", &issue).is_err());
+        assert!(IssueEdit::parse("---\ntitle: \n---\n\nBody\n", &issue).is_err());
+    }
+}
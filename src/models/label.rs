@@ -0,0 +1,23 @@
+use core::fmt;
+use serde::Deserialize;
+
+use super::searchable::Searchable;
+
+#[derive(Deserialize, Clone)]
+pub struct Label {
+    pub name: String,
+    /// Hex color without the leading `#`, e.g. `d73a4a`
+    pub color: String,
+}
+
+impl fmt::Display for Label {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
+
+impl Searchable for Label {
+    fn search_text(&self) -> String {
+        self.name.clone()
+    }
+}
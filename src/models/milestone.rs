@@ -0,0 +1,28 @@
+use chrono::{DateTime, Utc};
+use core::fmt;
+use serde::Deserialize;
+
+use super::searchable::Searchable;
+
+#[derive(Deserialize, Clone)]
+pub struct Milestone {
+    pub number: usize,
+    pub title: String,
+    pub state: String,
+    pub due_on: Option<DateTime<Utc>>,
+}
+
+impl fmt::Display for Milestone {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.due_on {
+            Some(due_on) => write!(f, "{} (due {})", self.title, due_on.format("%Y-%m-%d")),
+            None => write!(f, "{}", self.title),
+        }
+    }
+}
+
+impl Searchable for Milestone {
+    fn search_text(&self) -> String {
+        self.title.clone()
+    }
+}
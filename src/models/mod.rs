@@ -1,6 +1,25 @@
 pub mod app_state;
 pub mod args;
+pub mod comment;
 pub mod config;
+pub mod confirmation;
+pub mod conflict;
+pub mod detail_view;
+pub mod filters;
+pub mod focus;
 pub mod issue;
+pub mod issue_edit;
+pub mod label;
 pub mod menu_items;
+pub mod milestone;
+pub mod new_issue;
+pub mod picker;
+pub mod prompt;
+pub mod pull_request;
+pub mod repository;
+pub mod saved_view;
+pub mod search_query;
+pub mod search_results;
+pub mod searchable;
 pub mod stateful_list;
+pub mod user;
@@ -0,0 +1,102 @@
+use crate::error::{Error, Result};
+use serde::Serialize;
+
+/// The template opened in the editor to write a new issue.
+pub const NEW_ISSUE_TEMPLATE: &str = "---
+title: 
+labels: 
+assignees: 
+---
+
+";
+
+/// An issue to be created, as sent to the issues endpoint.
+#[derive(Serialize)]
+pub struct NewIssue {
+    pub title: String,
+    pub body: String,
+    pub labels: Vec<String>,
+    pub assignees: Vec<String>,
+}
+
+impl NewIssue {
+    /// Parse an edited `NEW_ISSUE_TEMPLATE`: front matter with the title, comma separated
+    /// labels and assignees, followed by the markdown body.
+    pub fn parse(content: &str) -> Result<Self> {
+        let mut new_issue = Self {
+            title: String::new(),
+            body: String::new(),
+            labels: Vec::new(),
+            assignees: Vec::new(),
+        };
+
+        let mut lines = content.lines();
+        if lines.next().map(|line| line.trim()) != Some("---") {
+            return Err(Error::Parse(String::from(
+                "The issue has to start with the --- front matter",
+            )));
+        }
+
+        for line in lines.by_ref() {
+            if line.trim() == "---" {
+                break;
+            }
+
+            let Some((key, value)) = line.split_once(':') else {
+                continue;
+            };
+
+            match key.trim() {
+                "title" => new_issue.title = value.trim().to_string(),
+                "labels" => new_issue.labels = split_list(value),
+                "assignees" => new_issue.assignees = split_list(value),
+                key => return Err(Error::Parse(format!("Unknown front matter key {:?}", key))),
+            }
+        }
+
+        new_issue.body = lines.collect::<Vec<&str>>().join("\n").trim().to_string();
+
+        if new_issue.title.is_empty() {
+            return Err(Error::Parse(String::from(
+                "No title given, the issue was not created",
+            )));
+        }
+
+        Ok(new_issue)
+    }
+}
+
+/// Split a comma separated front matter value, dropping `@` prefixes from user names.
+fn split_list(value: &str) -> Vec<String> {
+    value
+        .split(',')
+        .map(|item| item.trim().trim_start_matches('@').to_string())
+        .filter(|item| !item.is_empty())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_the_front_matter_and_body() {
+        let new_issue = NewIssue::parse(
+            "---\ntitle: Crash on start \nlabels: bug, ui,\nassignees: @octocat,hubot\n---\n\nIt crashes.\n\n---\n\nLog below\n",
+        )
+        .unwrap();
+
+        assert_eq!(new_issue.title, "Crash on start");
+        assert_eq!(new_issue.labels, ["bug", "ui"]);
+        assert_eq!(new_issue.assignees, ["octocat", "hubot"]);
+        // Only the first --- after the front matter ends it
+        assert_eq!(new_issue.body, "It crashes.\n\n---\n\nLog below");
+    }
+
+    #[test]
+    fn rejects_malformed_front_matter() {
+        assert!(NewIssue::parse("title: No front matter\n").is_err());
+        assert!(NewIssue::parse("---\ntitle: Typo\nlabel: bug\n---\n").is_err());
+        assert!(NewIssue::parse(NEW_ISSUE_TEMPLATE).is_err());
+    }
+}
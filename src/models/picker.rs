@@ -0,0 +1,79 @@
+use super::{
+    label::Label, milestone::Milestone, searchable::Searchable, stateful_list::StatefulList,
+    user::User,
+};
+
+/// The pickers that can be opened on the selected issue.
+pub enum IssuePicker {
+    Labels(Picker<Label>),
+    Assignees(Picker<User>),
+    Milestone(Picker<Milestone>),
+}
+
+/// A popup list of choices for the selected issue, narrowed by typing.
+pub struct Picker<T> {
+    pub title: String,
+    /// API url of the issue the choices are applied to
+    pub issue_url: String,
+    /// The `owner/name` the choices belong to
+    pub repository: String,
+    pub list: StatefulList<T>,
+    /// Keys of the checked items
+    pub checked: Vec<String>,
+    /// Set until the choices have been fetched
+    pub loading: bool,
+    /// Set if at most one item can be checked
+    pub single: bool,
+    key: fn(&T) -> String,
+}
+
+impl<T: Searchable> Picker<T> {
+    /// Open a picker with `checked` already checked, `key` identifies an item.
+    pub fn new(
+        title: &str,
+        issue_url: String,
+        repository: String,
+        checked: Vec<String>,
+        key: fn(&T) -> String,
+    ) -> Self {
+        Self {
+            title: title.to_string(),
+            issue_url,
+            repository,
+            list: StatefulList::with_items(Vec::new()),
+            checked,
+            loading: true,
+            single: false,
+            key,
+        }
+    }
+
+    /// Only allow one item to be checked at a time.
+    pub fn single(mut self) -> Self {
+        self.single = true;
+        self
+    }
+
+    /// Fill the picker once the choices are fetched.
+    pub fn set_items(&mut self, items: Vec<T>) {
+        self.list.replace_items(items, self.key);
+        self.loading = false;
+    }
+
+    pub fn is_checked(&self, item: &T) -> bool {
+        self.checked.contains(&(self.key)(item))
+    }
+
+    /// Check or uncheck the selected item.
+    pub fn toggle(&mut self) {
+        if let Some(key) = self.list.selected_item().map(self.key) {
+            match self.checked.iter().position(|checked| *checked == key) {
+                Some(index) => {
+                    self.checked.remove(index);
+                }
+                None if self.single => self.checked = vec![key],
+                None => self.checked.push(key),
+            }
+        }
+    }
+}
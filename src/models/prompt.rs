@@ -0,0 +1,26 @@
+/// What a confirmed prompt does with its input.
+pub enum PromptKind {
+    /// Create an issue in the repository typed in
+    CreateIssue,
+    /// Replace the issue list with the results of the Github search query typed in
+    SearchQuery,
+    /// Save the current filter or search query as a view named by the input
+    SaveView,
+}
+
+/// A single line text input shown in a popup.
+pub struct Prompt {
+    pub kind: PromptKind,
+    pub title: String,
+    pub input: String,
+}
+
+impl Prompt {
+    pub fn new(kind: PromptKind, title: &str, input: String) -> Self {
+        Self {
+            kind,
+            title: title.to_string(),
+            input,
+        }
+    }
+}
@@ -0,0 +1,30 @@
+use core::fmt;
+use serde::Deserialize;
+
+use super::searchable::Searchable;
+
+#[derive(Deserialize)]
+pub struct PullRequest {
+    pub html_url: String,
+    pub number: usize,
+    pub title: String,
+    pub body: Option<String>,
+    #[serde(default)]
+    pub draft: bool,
+}
+
+impl fmt::Display for PullRequest {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.draft {
+            write!(f, "{}: [draft] {}", self.number, self.title)
+        } else {
+            write!(f, "{}: {}", self.number, self.title)
+        }
+    }
+}
+
+impl Searchable for PullRequest {
+    fn search_text(&self) -> String {
+        format!("#{} {}", self.number, self.title)
+    }
+}
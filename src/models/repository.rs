@@ -0,0 +1,7 @@
+use serde::Deserialize;
+
+#[derive(Deserialize)]
+pub struct Repository {
+    /// The `owner/name` of the repository
+    pub full_name: String,
+}
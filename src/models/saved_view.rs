@@ -0,0 +1,15 @@
+use serde::{Deserialize, Serialize};
+
+use super::filters::Filters;
+
+/// A named filter or search query, shown as a tab beside the fixed menu items.
+#[derive(Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct SavedView {
+    pub name: String,
+    /// Github search query, the view lists the user's issues with `filters` if unset
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub query: Option<String>,
+    /// Filters of the issue list, only the sort order applies to a search query
+    pub filters: Filters,
+}
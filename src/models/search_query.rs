@@ -0,0 +1,34 @@
+use super::filters::{Direction, IssueSort};
+
+/// A Github search query whose results replace the issue list.
+pub struct SearchQuery {
+    pub query: String,
+    pub sort: Option<IssueSort>,
+    pub direction: Option<Direction>,
+    pub total_count: usize,
+    /// Set if the search timed out on Github's side and results may be missing
+    pub incomplete_results: bool,
+    /// Url of the next page of results, fetched once the selection nears the end of the list
+    pub next_url: Option<String>,
+}
+
+impl SearchQuery {
+    /// A search in the API's default order, best match first.
+    pub fn new(query: String) -> Self {
+        Self {
+            query,
+            sort: None,
+            direction: None,
+            total_count: 0,
+            incomplete_results: false,
+            next_url: None,
+        }
+    }
+
+    /// Order the results by `sort` in `direction`.
+    pub fn sorted(mut self, sort: Option<IssueSort>, direction: Option<Direction>) -> Self {
+        self.sort = sort;
+        self.direction = direction;
+        self
+    }
+}
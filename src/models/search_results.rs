@@ -0,0 +1,9 @@
+use serde::Deserialize;
+
+/// The envelope returned by the Github search endpoints.
+#[derive(Deserialize)]
+pub struct SearchResults<T> {
+    pub total_count: usize,
+    pub incomplete_results: bool,
+    pub items: Vec<T>,
+}
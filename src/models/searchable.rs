@@ -0,0 +1,19 @@
+/// Items that can be narrowed down by the search prompt.
+pub trait Searchable {
+    /// The text the search query is matched against.
+    fn search_text(&self) -> String;
+}
+
+/// Check if every whitespace separated term of `query` fuzzy matches `text`, meaning the
+/// characters of the term appear in `text` in order. Matching is case insensitive.
+pub fn fuzzy_match(query: &str, text: &str) -> bool {
+    let text = text.to_lowercase();
+
+    query.split_whitespace().all(|term| {
+        let mut chars = text.chars();
+
+        term.to_lowercase()
+            .chars()
+            .all(|term_char| chars.any(|char| char == term_char))
+    })
+}
@@ -1,8 +1,14 @@
 use tui::widgets::ListState;
 
+use super::searchable::{fuzzy_match, Searchable};
+
 pub struct StatefulList<T> {
+    /// Selection within the visible items
     pub state: ListState,
     pub items: Vec<T>,
+    /// Indexes into `items` of the items matching `query`
+    pub visible: Vec<usize>,
+    pub query: String,
 }
 
 impl<T> StatefulList<T> {
@@ -10,7 +16,9 @@ impl<T> StatefulList<T> {
     pub fn with_items(items: Vec<T>) -> Self {
         let mut list = Self {
             state: ListState::default(),
+            visible: (0..items.len()).collect(),
             items,
+            query: String::new(),
         };
 
         list.next();
@@ -22,7 +30,7 @@ impl<T> StatefulList<T> {
     pub fn next(&mut self) {
         let i = match self.state.selected() {
             Some(i) => {
-                if i >= self.items.len() - 1 {
+                if i >= self.visible.len().saturating_sub(1) {
                     i
                 } else {
                     i + 1
@@ -50,8 +58,161 @@ impl<T> StatefulList<T> {
         self.state.select(Some(i))
     }
 
-    /// Return the current selected item.
-    pub fn selected(&mut self) -> Option<usize> {
-        self.state.selected()
+    /// Return the index into `items` of the current selected item.
+    pub fn selected(&self) -> Option<usize> {
+        self.state
+            .selected()
+            .and_then(|index| self.visible.get(index).copied())
+    }
+
+    /// Return a reference to the current selected item.
+    pub fn selected_item(&self) -> Option<&T> {
+        self.selected().and_then(|index| self.items.get(index))
+    }
+
+    /// Iterate over the items matching the current query.
+    pub fn visible_items(&self) -> impl Iterator<Item = &T> {
+        self.visible
+            .iter()
+            .filter_map(|index| self.items.get(*index))
+    }
+}
+
+impl<T: Searchable> StatefulList<T> {
+    /// Filter the visible items with `query`, keeping the selected item selected if it still
+    /// matches.
+    pub fn set_query(&mut self, query: &str) {
+        query.clone_into(&mut self.query);
+        self.apply_query();
+    }
+
+    /// Recompute the visible items, e.g. after `items` changed.
+    pub fn apply_query(&mut self) {
+        let selected = self.selected();
+        self.filter_visible();
+        self.select_item(selected);
+    }
+
+    /// Replace the items, keeping the selected item selected if it is still present according
+    /// to `key`.
+    pub fn replace_items<K: PartialEq>(&mut self, items: Vec<T>, key: impl Fn(&T) -> K) {
+        let selected_key = self.selected_item().map(&key);
+
+        self.items = items;
+        self.filter_visible();
+
+        let selected = selected_key
+            .and_then(|selected_key| self.items.iter().position(|item| key(item) == selected_key));
+        self.select_item(selected);
+    }
+
+    /// Append `items`, e.g. the next page of a listing, keeping the selection.
+    pub fn extend(&mut self, items: Vec<T>) {
+        self.items.extend(items);
+        self.apply_query();
+    }
+
+    /// Insert `item` at `index` in `items` and select it.
+    pub fn insert_and_select(&mut self, index: usize, item: T) {
+        self.items.insert(index, item);
+        self.filter_visible();
+        self.select_item(Some(index));
+    }
+
+    fn filter_visible(&mut self) {
+        self.visible = self
+            .items
+            .iter()
+            .enumerate()
+            .filter(|(_, item)| fuzzy_match(&self.query, &item.search_text()))
+            .map(|(index, _)| index)
+            .collect();
+    }
+
+    /// Select the item at `index` in `items`, or the first visible item if it is not visible.
+    fn select_item(&mut self, index: Option<usize>) {
+        let position = index
+            .and_then(|index| self.visible.iter().position(|visible| *visible == index))
+            .or(if self.visible.is_empty() {
+                None
+            } else {
+                Some(0)
+            });
+
+        self.state.select(position);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Item(&'static str);
+
+    impl Searchable for Item {
+        fn search_text(&self) -> String {
+            self.0.to_string()
+        }
+    }
+
+    fn list() -> StatefulList<Item> {
+        StatefulList::with_items(vec![
+            Item("fix crash"),
+            Item("add labels"),
+            Item("fix typo"),
+            Item("add views"),
+        ])
+    }
+
+    #[test]
+    fn selection_maps_to_the_filtered_items() {
+        let mut list = list();
+
+        list.set_query("fix");
+        assert_eq!(list.selected(), Some(0));
+        list.next();
+        assert_eq!(list.selected(), Some(2));
+        assert_eq!(list.selected_item().map(|item| item.0), Some("fix typo"));
+    }
+
+    #[test]
+    fn selection_survives_query_edits_while_it_matches() {
+        let mut list = list();
+        list.next();
+        list.next();
+
+        list.set_query("f");
+        assert_eq!(list.selected(), Some(2));
+        list.set_query("fix t");
+        assert_eq!(list.selected(), Some(2));
+
+        // The selected item no longer matches, the first visible one is selected
+        list.set_query("add");
+        assert_eq!(list.selected(), Some(1));
+    }
+
+    #[test]
+    fn nothing_is_selected_without_matches() {
+        let mut list = list();
+
+        list.set_query("zzz");
+        assert_eq!(list.selected(), None);
+        assert!(list.selected_item().is_none());
+
+        list.replace_items(Vec::new(), |item| item.0);
+        list.set_query("");
+        assert_eq!(list.selected(), None);
+    }
+
+    #[test]
+    fn replacing_items_keeps_the_selected_item() {
+        let mut list = list();
+        list.next();
+
+        list.replace_items(
+            vec![Item("new issue"), Item("fix crash"), Item("add labels")],
+            |item| item.0,
+        );
+        assert_eq!(list.selected(), Some(2));
     }
 }
@@ -0,0 +1,21 @@
+use core::fmt;
+use serde::Deserialize;
+
+use super::searchable::Searchable;
+
+#[derive(Deserialize, Clone)]
+pub struct User {
+    pub login: String,
+}
+
+impl fmt::Display for User {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "@{}", self.login)
+    }
+}
+
+impl Searchable for User {
+    fn search_text(&self) -> String {
+        self.login.clone()
+    }
+}
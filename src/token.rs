@@ -0,0 +1,222 @@
+use std::{
+    env, fmt, fs,
+    io::Write,
+    path::PathBuf,
+    process::{Command, Stdio},
+};
+
+/// Where the access token was found.
+pub enum TokenSource {
+    Flag,
+    Env(&'static str),
+    GhCli(PathBuf),
+    GitCredential,
+    ConfigFile,
+}
+
+impl fmt::Display for TokenSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Flag => write!(f, "the --token flag"),
+            Self::Env(name) => write!(f, "the {} environment variable", name),
+            Self::GhCli(path) => write!(f, "the gh CLI config at {}", path.display()),
+            Self::GitCredential => write!(f, "git credential fill"),
+            Self::ConfigFile => write!(f, "the config file"),
+        }
+    }
+}
+
+/// Find the access token for `host`, trying in order the `--token` flag, the `GITHUB_TOKEN` and
+/// `GH_TOKEN` environment variables (`GITHUB_ENTERPRISE_TOKEN` and `GH_ENTERPRISE_TOKEN` for other
+/// hosts than github.com), the gh CLI's `hosts.yml`, `git credential fill` and at last the token
+/// stored in the config file.
+pub fn resolve_token(
+    flag: Option<String>,
+    host: &str,
+    config_token: &str,
+) -> Option<(String, TokenSource)> {
+    if let Some(token) = flag.filter(|token| !token.is_empty()) {
+        return Some((token, TokenSource::Flag));
+    }
+
+    // Like the gh CLI, the plain variables only hold a github.com token
+    let env_names = if host == "github.com" {
+        ["GITHUB_TOKEN", "GH_TOKEN"]
+    } else {
+        ["GITHUB_ENTERPRISE_TOKEN", "GH_ENTERPRISE_TOKEN"]
+    };
+
+    for name in env_names {
+        if let Some(token) = env::var(name).ok().filter(|token| !token.is_empty()) {
+            return Some((token, TokenSource::Env(name)));
+        }
+    }
+
+    if let Some(path) = gh_hosts_path() {
+        let token = fs::read_to_string(&path)
+            .ok()
+            .and_then(|hosts| gh_hosts_token(&hosts, host));
+
+        if let Some(token) = token {
+            return Some((token, TokenSource::GhCli(path)));
+        }
+    }
+
+    if let Some(token) = git_credential_token(host) {
+        return Some((token, TokenSource::GitCredential));
+    }
+
+    if !config_token.is_empty() {
+        return Some((config_token.to_string(), TokenSource::ConfigFile));
+    }
+
+    None
+}
+
+/// Prefixes Github gives its token types.
+const TOKEN_PREFIXES: [&str; 6] = ["github_pat_", "ghp_", "gho_", "ghu_", "ghs_", "ghr_"];
+
+/// Hide all of `token` but its type prefix, e.g. `ghp_********`.
+pub fn redact(token: &str) -> String {
+    let prefix = TOKEN_PREFIXES
+        .iter()
+        .find(|prefix| token.starts_with(*prefix))
+        .unwrap_or(&"");
+
+    format!("{}********", prefix)
+}
+
+/// The gh CLI's hosts file, following its `GH_CONFIG_DIR` and `XDG_CONFIG_HOME` lookup.
+fn gh_hosts_path() -> Option<PathBuf> {
+    let config_dir = if let Ok(dir) = env::var("GH_CONFIG_DIR") {
+        PathBuf::from(dir)
+    } else if let Ok(dir) = env::var("XDG_CONFIG_HOME") {
+        PathBuf::from(dir).join("gh")
+    } else if let Ok(dir) = env::var("AppData") {
+        PathBuf::from(dir).join("GitHub CLI")
+    } else {
+        PathBuf::from(env::var("HOME").ok()?)
+            .join(".config")
+            .join("gh")
+    };
+
+    Some(config_dir.join("hosts.yml"))
+}
+
+/// The `oauth_token` of `host` in the contents of the gh CLI's `hosts.yml`.
+///
+/// Only the simple mapping gh writes is understood: hosts at the top level, with the token
+/// anywhere in the indented block below.
+fn gh_hosts_token(hosts: &str, host: &str) -> Option<String> {
+    let mut in_host = false;
+
+    for line in hosts.lines() {
+        if line.trim().is_empty() || line.trim_start().starts_with('#') {
+            continue;
+        }
+
+        if !line.starts_with(char::is_whitespace) {
+            in_host = line.trim_end().trim_end_matches(':').trim_matches('"') == host;
+            continue;
+        }
+
+        if in_host {
+            if let Some((key, value)) = line.trim().split_once(':') {
+                let value = value.trim().trim_matches('"').trim_matches('\'');
+
+                if key == "oauth_token" && !value.is_empty() {
+                    return Some(value.to_string());
+                }
+            }
+        }
+    }
+
+    None
+}
+
+/// Ask git's credential helpers for the password of `host`, without prompting.
+fn git_credential_token(host: &str) -> Option<String> {
+    let mut child = Command::new("git")
+        .args(["credential", "fill"])
+        .env("GIT_TERMINAL_PROMPT", "0")
+        .env("GIT_ASKPASS", "")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .ok()?;
+
+    child
+        .stdin
+        .take()?
+        .write_all(format!("protocol=https\nhost={}\n\n", host).as_bytes())
+        .ok()?;
+
+    let output = child.wait_with_output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+
+    credential_password(&String::from_utf8_lossy(&output.stdout))
+}
+
+/// The password in the output of `git credential fill`.
+fn credential_password(output: &str) -> Option<String> {
+    output
+        .lines()
+        .find_map(|line| line.strip_prefix("password="))
+        .filter(|password| !password.is_empty())
+        .map(str::to_string)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_the_token_of_the_host_in_gh_hosts() {
+        let hosts = "github.example.com:
+    oauth_token: ghe_token
+    user: octocat
+github.com:
+    users:
+        octocat:
+            oauth_token: gho_new
+    git_protocol: https
+    user: octocat
+    oauth_token: gho_old
+";
+
+        assert_eq!(
+            gh_hosts_token(hosts, "github.com").as_deref(),
+            Some("gho_new")
+        );
+        assert_eq!(
+            gh_hosts_token(hosts, "github.example.com").as_deref(),
+            Some("ghe_token")
+        );
+        assert_eq!(gh_hosts_token(hosts, "gitlab.com"), None);
+    }
+
+    #[test]
+    fn reads_the_password_from_git_credential_output() {
+        assert_eq!(
+            credential_password(
+                "protocol=https\nhost=github.com\nusername=octocat\npassword=secret\n"
+            )
+            .as_deref(),
+            Some("secret")
+        );
+        assert_eq!(
+            credential_password("protocol=https\nhost=github.com\n"),
+            None
+        );
+    }
+
+    #[test]
+    fn redacts_all_but_the_token_type() {
+        assert_eq!(redact("ghp_abcdef123456"), "ghp_********");
+        assert_eq!(redact("github_pat_abcdef"), "github_pat_********");
+        assert_eq!(redact("0123456789abcdef"), "********");
+    }
+}
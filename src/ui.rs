@@ -1,16 +1,30 @@
-use ansi_to_tui::IntoText;
 use tui::{
     backend::Backend,
-    layout::{Alignment, Constraint, Corner, Layout},
-    style::{Color, Style},
+    layout::{Alignment, Constraint, Corner, Layout, Rect},
+    style::{Color, Modifier, Style},
     text::{Span, Spans, Text},
-    widgets::{Block, Borders, List, ListItem, Paragraph, Wrap},
+    widgets::{Block, Borders, Clear, List, ListItem, Paragraph, Wrap},
     Frame,
 };
 
-use crate::{models::stateful_list::StatefulList, AppState, MenuItems};
+use crate::{
+    diff::DiffLine,
+    markdown::markdown_to_text,
+    models::{
+        config::Config,
+        confirmation::{Confirmation, IssueAction, LOCK_REASONS},
+        conflict::Conflict,
+        detail_view::DetailView,
+        focus::Focus,
+        picker::{IssuePicker, Picker},
+        prompt::Prompt,
+        searchable::Searchable,
+        stateful_list::StatefulList,
+    },
+    AppState, Issue, MenuItems,
+};
 
-pub fn ui<B: Backend>(f: &mut Frame<B>, app_state: &mut AppState) {
+pub fn ui<B: Backend>(f: &mut Frame<B>, app_state: &mut AppState, config: &Config) {
     let size = f.size();
 
     // The main canvas
@@ -29,118 +43,638 @@ pub fn ui<B: Backend>(f: &mut Frame<B>, app_state: &mut AppState) {
         .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
         .split(main[1]);
 
-    f.render_widget(render_menu_bar(&app_state), main[0]);
-    f.render_stateful_widget(
-        render_list(&app_state.issues),
-        inner[0],
-        &mut app_state.issues.state,
-    );
-    f.render_widget(
-        render_markdown(if let Some(index) = app_state.issues.selected() {
-            match app_state.issues.items.get(index) {
-                Some(issue) => issue.body.as_str(),
-                None => "",
+    // Bottom bar split between the controls and the status
+    let bottom = Layout::default()
+        .direction(tui::layout::Direction::Horizontal)
+        .constraints([Constraint::Percentage(70), Constraint::Percentage(30)])
+        .split(main[2]);
+
+    f.render_widget(render_menu_bar(app_state, config), main[0]);
+    f.render_widget(render_status(app_state), bottom[1]);
+
+    // The detail view takes over the whole list and preview area
+    if let Some(detail_view) = &app_state.detail_view {
+        if let Some(issue) = app_state.detail_issue() {
+            f.render_widget(render_detail_view(issue, detail_view), main[1]);
+            f.render_widget(render_detail_controls(), bottom[0]);
+            return;
+        }
+    }
+
+    let list_focused = app_state.focus == Focus::List;
+
+    // Each tab keeps its own list, selection and preview
+    let preview = match app_state.current_menu {
+        MenuItems::Issues => {
+            let warning = if app_state.skipped_issues.is_empty() {
+                None
+            } else {
+                Some(format!(
+                    "{} issues could not be parsed, !: details",
+                    app_state.skipped_issues.len()
+                ))
+            };
+
+            f.render_stateful_widget(
+                render_list(&app_state.issues, warning, list_focused),
+                inner[0],
+                &mut app_state.issues.state,
+            );
+
+            match app_state.issues.selected_item() {
+                Some(issue) => issue_preview_text(issue),
+                None => Text::default(),
             }
-        } else {
-            ""
-        }),
+        }
+        MenuItems::PullRequests => {
+            f.render_stateful_widget(
+                render_list(&app_state.pull_requests, None, list_focused),
+                inner[0],
+                &mut app_state.pull_requests.state,
+            );
+
+            markdown_to_text(
+                app_state
+                    .pull_requests
+                    .selected_item()
+                    .and_then(|pull_request| pull_request.body.as_deref())
+                    .unwrap_or(""),
+            )
+        }
+    };
+
+    // Clamp the scroll offset so scrolling to the bottom stops at the last line
+    let content_width = inner[1].width.saturating_sub(2);
+    app_state.preview_height = inner[1].height.saturating_sub(2);
+    let max_scroll =
+        wrapped_height(&preview, content_width).saturating_sub(app_state.preview_height);
+    if app_state.preview_scroll() > max_scroll {
+        app_state.set_preview_scroll(max_scroll);
+    }
+
+    f.render_widget(
+        render_preview(preview, app_state.preview_scroll(), !list_focused),
         inner[1],
     );
-    f.render_widget(render_controls(), main[2]);
-}
 
-fn parse_markdown_headers(content: &str) -> String {
-    content
-        .lines()
-        .map(|line| {
-            let mut parsed_content = String::new();
+    f.render_widget(render_controls(app_state), bottom[0]);
 
-            // Check if line starts with '#'
-            if line.chars().nth(0).unwrap_or(' ') != '#' {
-                return format!("{}\n", line);
-            }
+    // Popups are drawn last, over everything else
+    if let Some(prompt) = &app_state.prompt {
+        let area = centered_rect(60, 3, size);
+        f.render_widget(Clear, area);
+        f.render_widget(render_prompt(prompt), area);
+    }
 
-            // Parse markdown headings
-            match String::from_iter(line.chars().take(6).filter(|char| char == &'#')).as_str() {
-                "#" => {
-                    parsed_content
-                        .push_str(format!("\x1b[1;32m██ {}\x1b[0m\n", line[1..].trim()).as_str());
-                }
-                "##" => {
-                    parsed_content
-                        .push_str(format!("\x1b[1;36m▓▓▓ {}\x1b[0m\n", line[2..].trim()).as_str());
-                }
-                "###" => {
-                    parsed_content
-                        .push_str(format!("\x1b[1;33m▒▒▒▒ {}\x1b[0m\n", line[3..].trim()).as_str());
-                }
-                "####" => {
-                    parsed_content.push_str(
-                        format!("\x1b[1;35m░░░░░ {}\x1b[0m\n", line[4..].trim()).as_str(),
-                    );
-                }
-                "#####" => {
-                    parsed_content.push_str(format!("{}\n", line[5..].trim()).as_str());
-                }
-                "######" => {
-                    parsed_content.push_str(format!("{}\n", line[6..].trim()).as_str());
-                }
-                _ => parsed_content.push_str(format!("{}\n", line).as_str()),
-            }
+    if let Some(picker) = app_state.picker.as_mut() {
+        let area = centered_rect(50, size.height * 6 / 10, size);
+        f.render_widget(Clear, area);
+
+        match picker {
+            IssuePicker::Labels(picker) => render_picker(f, area, picker, |label| {
+                Style::default().fg(label_color(&label.color))
+            }),
+            IssuePicker::Assignees(picker) => render_picker(f, area, picker, |_| Style::default()),
+            IssuePicker::Milestone(picker) => render_picker(f, area, picker, |_| Style::default()),
+        }
+    }
+
+    if let Some(conflict) = &app_state.conflict {
+        let area = centered_rect(80, size.height * 8 / 10, size);
+        f.render_widget(Clear, area);
+        f.render_widget(render_conflict(conflict), area);
+    }
+
+    if let Some(confirmation) = &app_state.confirmation {
+        let area = centered_rect(60, 6, size);
+        f.render_widget(Clear, area);
+        f.render_widget(render_confirmation(confirmation), area);
+    }
 
-            parsed_content
+    // Errors show as a toast in the corner until dismissed
+    if let Some(error) = &app_state.error {
+        let width = (main[1].width * 4 / 10).max(20).min(main[1].width);
+        let height = (wrapped_height(&Text::raw(error.as_str()), width.saturating_sub(2)) + 2)
+            .min(main[1].height);
+        let area = Rect {
+            x: main[1].x + main[1].width - width,
+            y: main[1].y + main[1].height - height,
+            width,
+            height,
+        };
+        f.render_widget(Clear, area);
+        f.render_widget(render_toast(error), area);
+    }
+}
+
+fn render_toast<'a>(error: &str) -> Paragraph<'a> {
+    Paragraph::new(error.to_string())
+        .wrap(Wrap { trim: true })
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Red))
+                .title(Span::styled(
+                    " Error, Esc: dismiss ",
+                    Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+                )),
+        )
+}
+
+/// Render a picker popup in `area`, `style` colors each choice.
+fn render_picker<B: Backend, T: std::fmt::Display + Searchable>(
+    f: &mut Frame<B>,
+    area: Rect,
+    picker: &mut Picker<T>,
+    style: impl Fn(&T) -> Style,
+) {
+    let items: Vec<ListItem> = picker
+        .list
+        .visible_items()
+        .map(|item| {
+            ListItem::new(Spans::from(vec![
+                Span::raw(if picker.is_checked(item) {
+                    "[x] "
+                } else {
+                    "[ ] "
+                }),
+                Span::styled(item.to_string(), style(item)),
+            ]))
         })
-        .collect::<Vec<String>>()
-        .join("")
+        .collect();
+
+    let title = if picker.loading {
+        format!(" {}: loading.. ", picker.title)
+    } else if picker.list.query.is_empty() {
+        format!(" {} ", picker.title)
+    } else {
+        format!(" {}: {} ", picker.title, picker.list.query)
+    };
+
+    let list = List::new(items)
+        .highlight_style(Style::default().add_modifier(Modifier::REVERSED))
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::LightBlue))
+                .title(title),
+        );
+
+    f.render_stateful_widget(list, area, &mut picker.list.state);
 }
 
-fn render_list<'a, T: std::fmt::Display>(items: &StatefulList<T>) -> List<'a> {
-    let items: Vec<ListItem> = items
-        .items
-        .iter()
+fn render_confirmation<'a>(confirmation: &Confirmation) -> Paragraph<'a> {
+    let mut lines = vec![Spans::from(vec![
+        Span::styled(
+            format!("{} ", confirmation.action),
+            Style::default().add_modifier(Modifier::BOLD),
+        ),
+        Span::raw(format!("{}?", confirmation.issue_name)),
+    ])];
+
+    if let IssueAction::Lock(reason) = confirmation.action {
+        let mut reasons = vec![Span::raw("Reason: ")];
+
+        for (index, name) in std::iter::once("none").chain(LOCK_REASONS).enumerate() {
+            let selected = match reason {
+                Some(reason) => reason + 1 == index,
+                None => index == 0,
+            };
+
+            reasons.push(Span::styled(
+                format!(" {} ", name),
+                if selected {
+                    Style::default().fg(Color::Black).bg(Color::LightBlue)
+                } else {
+                    Style::default()
+                },
+            ));
+        }
+
+        lines.push(Spans::from(reasons));
+    }
+
+    lines.push(Spans::default());
+    lines.push(Spans::from(Span::styled(
+        if matches!(confirmation.action, IssueAction::Lock(_)) {
+            "y / Enter: confirm, n / Esc: cancel, Left / Right: change reason"
+        } else {
+            "y / Enter: confirm, n / Esc: cancel"
+        },
+        Style::default().fg(Color::DarkGray),
+    )));
+
+    Paragraph::new(lines).wrap(Wrap { trim: false }).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Yellow))
+            .title(" Confirm "),
+    )
+}
+
+fn render_conflict<'a>(conflict: &Conflict) -> Paragraph<'a> {
+    let mut lines = vec![
+        Spans::from(vec![
+            Span::styled(
+                conflict.issue_name.clone(),
+                Style::default().add_modifier(Modifier::BOLD),
+            ),
+            Span::raw(" changed on the server since it was opened."),
+        ]),
+        Spans::from(vec![
+            Span::styled("- on the server", Style::default().fg(Color::Red)),
+            Span::raw(", "),
+            Span::styled("+ your edit", Style::default().fg(Color::Green)),
+        ]),
+        Spans::default(),
+    ];
+
+    lines.extend(conflict.diff.iter().map(|line| match line {
+        DiffLine::Same(text) => Spans::from(format!("  {}", text)),
+        DiffLine::Removed(text) => Spans::from(Span::styled(
+            format!("- {}", text),
+            Style::default().fg(Color::Red),
+        )),
+        DiffLine::Added(text) => Spans::from(Span::styled(
+            format!("+ {}", text),
+            Style::default().fg(Color::Green),
+        )),
+    }));
+
+    Paragraph::new(lines)
+        .wrap(Wrap { trim: false })
+        .scroll((conflict.scroll, 0))
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Yellow))
+                .title(" Conflict: o: overwrite, e: edit again, a / Esc: abort "),
+        )
+}
+
+/// A rectangle `percent_x` percent wide and `height` rows high in the middle of `area`.
+fn centered_rect(percent_x: u16, height: u16, area: Rect) -> Rect {
+    let width = area.width * percent_x / 100;
+
+    Rect {
+        x: area.x + (area.width - width) / 2,
+        y: area.y + area.height.saturating_sub(height) / 2,
+        width,
+        height: height.min(area.height),
+    }
+}
+
+fn render_prompt<'a>(prompt: &Prompt) -> Paragraph<'a> {
+    Paragraph::new(Spans::from(vec![
+        Span::raw(prompt.input.clone()),
+        Span::styled("█", Style::default().fg(Color::LightBlue)),
+    ]))
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::LightBlue))
+            .title(format!(" {} ", prompt.title)),
+    )
+}
+
+fn render_list<'a, T: std::fmt::Display>(
+    list: &StatefulList<T>,
+    warning: Option<String>,
+    focused: bool,
+) -> List<'a> {
+    let items: Vec<ListItem> = list
+        .visible_items()
         .map(|item| ListItem::new(item.to_string()))
         .collect();
 
+    let mut title = vec![];
+
+    if !list.query.is_empty() {
+        title.push(Span::styled(
+            format!(
+                " /{} ({} of {}) ",
+                list.query,
+                list.visible.len(),
+                list.items.len()
+            ),
+            Style::default().fg(Color::LightBlue),
+        ));
+    }
+
+    if let Some(warning) = warning {
+        title.push(Span::styled(
+            format!(" {} ", warning),
+            Style::default().fg(Color::Yellow),
+        ));
+    }
+
     List::new(items)
         .highlight_style(Style::default().fg(Color::LightGreen))
         .start_corner(Corner::TopLeft)
-        .block(Block::default().borders(Borders::ALL))
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(focus_style(focused))
+                .title(title),
+        )
+}
+
+/// Border style marking the pane that receives the navigation keys.
+fn focus_style(focused: bool) -> Style {
+    if focused {
+        Style::default().fg(Color::LightGreen)
+    } else {
+        Style::default()
+    }
 }
 
-fn render_markdown<'a>(content: &'a str) -> Paragraph<'a> {
-    let parsed_content = parse_markdown_headers(content);
+/// Approximate the number of rows `text` takes once wrapped to `width` columns.
+fn wrapped_height(text: &Text, width: u16) -> u16 {
+    let width = width.max(1) as usize;
 
-    // Convert md content to ansi string
-    let output = termimad::text(&parsed_content.as_str())
-        .to_string()
-        // Convert ansi string to tui::text::Text
-        .into_text()
-        .unwrap_or(Text::from(content));
+    text.lines
+        .iter()
+        .map(|line| line.width().max(1).div_ceil(width))
+        .sum::<usize>()
+        .min(u16::MAX as usize) as u16
+}
 
-    Paragraph::new(output)
+fn render_preview<'a>(text: Text<'a>, scroll: u16, focused: bool) -> Paragraph<'a> {
+    Paragraph::new(text)
         .wrap(Wrap { trim: false })
+        .scroll((scroll, 0))
+        .alignment(Alignment::Left)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(focus_style(focused)),
+        )
+}
+
+/// Convert a Github label hex color to a terminal color.
+fn label_color(color: &str) -> Color {
+    match (
+        u8::from_str_radix(color.get(0..2).unwrap_or(""), 16),
+        u8::from_str_radix(color.get(2..4).unwrap_or(""), 16),
+        u8::from_str_radix(color.get(4..6).unwrap_or(""), 16),
+    ) {
+        (Ok(r), Ok(g), Ok(b)) => Color::Rgb(r, g, b),
+        _ => Color::White,
+    }
+}
+
+/// The title, state, people, labels and timestamps shown above an issue's body.
+fn issue_header(issue: &Issue) -> Text<'static> {
+    let mut header = vec![
+        Spans::from(Span::styled(
+            format!("#{} {}", issue.number, issue.title),
+            Style::default().add_modifier(Modifier::BOLD),
+        )),
+        Spans::from(vec![
+            Span::styled(
+                match issue.state_reason.as_deref() {
+                    Some("not_planned") if issue.state == "closed" => {
+                        String::from("closed as not planned")
+                    }
+                    _ => issue.state.clone(),
+                },
+                Style::default().fg(if issue.state == "open" {
+                    Color::Green
+                } else {
+                    Color::Magenta
+                }),
+            ),
+            Span::raw(format!(" · opened by {}", issue.user)),
+            Span::raw(match &issue.repository {
+                Some(repository) => format!(" in {}", repository.full_name),
+                None => String::new(),
+            }),
+            Span::styled(
+                match (issue.locked, &issue.active_lock_reason) {
+                    (true, Some(reason)) => format!(" · locked as {}", reason),
+                    (true, None) => String::from(" · locked"),
+                    (false, _) => String::new(),
+                },
+                Style::default().fg(Color::Yellow),
+            ),
+        ]),
+        Spans::from(Span::styled(
+            format!(
+                "created {} · updated {} · {} comments",
+                issue.created_at.format("%Y-%m-%d %H:%M"),
+                issue.updated_at.format("%Y-%m-%d %H:%M"),
+                issue.comments
+            ),
+            Style::default().fg(Color::DarkGray),
+        )),
+    ];
+
+    if !issue.assignees.is_empty() {
+        header.push(Spans::from(format!(
+            "assignees: {}",
+            issue
+                .assignees
+                .iter()
+                .map(|user| user.to_string())
+                .collect::<Vec<String>>()
+                .join(", ")
+        )));
+    }
+
+    if let Some(milestone) = &issue.milestone {
+        header.push(Spans::from(format!("milestone: {}", milestone)));
+    }
+
+    if !issue.labels.is_empty() {
+        header.push(Spans::from(
+            issue
+                .labels
+                .iter()
+                .flat_map(|label| {
+                    vec![
+                        Span::styled(
+                            format!(" {} ", label.name),
+                            Style::default().fg(label_color(&label.color)),
+                        ),
+                        Span::raw(" "),
+                    ]
+                })
+                .collect::<Vec<Span>>(),
+        ));
+    }
+
+    header.push(Spans::default());
+
+    Text::from(header)
+}
+
+/// The rendered issue body, or a placeholder when it has no description.
+fn render_issue_body(issue: &Issue) -> Text<'static> {
+    match issue.body.as_deref() {
+        Some(body) if !body.trim().is_empty() => markdown_to_text(body),
+        _ => Text::styled(
+            "No description provided",
+            Style::default()
+                .fg(Color::DarkGray)
+                .add_modifier(Modifier::ITALIC),
+        ),
+    }
+}
+
+fn issue_preview_text(issue: &Issue) -> Text<'static> {
+    let mut text = issue_header(issue);
+    text.extend(render_issue_body(issue));
+
+    text
+}
+
+fn render_detail_view<'a>(issue: &Issue, detail_view: &DetailView) -> Paragraph<'a> {
+    let mut text = issue_header(issue);
+
+    text.extend(render_issue_body(issue));
+
+    for comment in &detail_view.comments {
+        text.extend(Text::from(vec![
+            Spans::default(),
+            Spans::from(Span::styled(
+                format!(
+                    "{} commented on {}",
+                    comment.user,
+                    comment.created_at.format("%Y-%m-%d %H:%M")
+                ),
+                Style::default()
+                    .fg(Color::Cyan)
+                    .add_modifier(Modifier::BOLD),
+            )),
+        ]));
+        text.extend(markdown_to_text(&comment.body));
+    }
+
+    if detail_view.comments.is_empty() {
+        text.extend(Text::from(vec![
+            Spans::default(),
+            Spans::from(Span::styled(
+                if detail_view.loading {
+                    "Loading comments.."
+                } else {
+                    "No comments"
+                },
+                Style::default().fg(Color::DarkGray),
+            )),
+        ]));
+    }
+
+    Paragraph::new(text)
+        .wrap(Wrap { trim: false })
+        .scroll((detail_view.scroll, 0))
         .alignment(Alignment::Left)
         .block(Block::default().borders(Borders::ALL))
 }
 
-fn render_controls<'a>() -> Paragraph<'a> {
-    Paragraph::new("q: quit, Up / k && Down / j: scroll list, Enter: open issue")
+/// The spinner and progress of the running network tasks, or the search progress and the
+/// remaining requests of the rate limit.
+fn render_status<'a>(app_state: &AppState) -> Paragraph<'a> {
+    const SPINNER: [&str; 10] = ["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
+
+    let status = if !app_state.tasks.is_empty() {
+        let mut messages: Vec<&str> = app_state
+            .tasks
+            .values()
+            .map(|message| message.as_str())
+            .collect();
+        messages.sort();
+
+        Spans::from(Span::raw(format!(
+            "{} {}",
+            SPINNER[app_state.spinner_frame % SPINNER.len()],
+            messages.join(", ")
+        )))
+    } else {
+        let mut spans = Vec::new();
+
+        if let Some(search) = &app_state.search_query {
+            spans.push(Span::raw(format!(
+                "{} of {} results",
+                app_state.issues.items.len(),
+                search.total_count
+            )));
+
+            if search.incomplete_results {
+                spans.push(Span::styled(
+                    " (incomplete)",
+                    Style::default().fg(Color::Yellow),
+                ));
+            }
+        }
+
+        if let Some(rate_limit) = &app_state.rate_limit {
+            // Warn once less than a tenth of the requests are left
+            let color = if rate_limit.remaining == 0 {
+                Color::Red
+            } else if rate_limit.remaining < rate_limit.limit / 10 {
+                Color::Yellow
+            } else {
+                Color::DarkGray
+            };
+
+            spans.push(Span::styled(
+                format!(
+                    "  {}/{} requests left",
+                    rate_limit.remaining, rate_limit.limit
+                ),
+                Style::default().fg(color),
+            ));
+        }
+
+        Spans::from(spans)
+    };
+
+    Paragraph::new(status).alignment(Alignment::Right)
+}
+
+fn render_detail_controls<'a>() -> Paragraph<'a> {
+    Paragraph::new("q: quit, Esc: back, Up / k && Down / j: scroll, o: open in browser, m: comment")
         .wrap(Wrap { trim: false })
         .alignment(Alignment::Left)
 }
 
-fn render_menu_bar<'a>(app_state: &AppState) -> Paragraph<'a> {
-    let items = vec![Spans::from(
+fn render_controls<'a>(app_state: &AppState) -> Paragraph<'a> {
+    if app_state.searching {
+        let query = match app_state.current_menu {
+            MenuItems::Issues => &app_state.issues.query,
+            MenuItems::PullRequests => &app_state.pull_requests.query,
+        };
+
+        return Paragraph::new(Spans::from(vec![
+            Span::styled(format!("/{}", query), Style::default().fg(Color::LightBlue)),
+            Span::styled("█", Style::default().fg(Color::LightBlue)),
+            Span::raw("  Enter: keep filter, Esc: clear filter"),
+        ]))
+        .alignment(Alignment::Left);
+    }
+
+    Paragraph::new(
+        "q: quit, I / P: switch tab, Up / k && Down / j: scroll list, Enter: open issue, o: open in browser, /: filter, Esc: clear filter, s: Github search, 1-9: views, V: save view, p: switch profile, Tab: focus preview, r: refresh, c: create issue, m: comment, x / X: close or reopen, L: lock, e: edit, l: labels, a: assignees, M: milestone",
+    )
+    .wrap(Wrap { trim: false })
+    .alignment(Alignment::Left)
+}
+
+fn render_menu_bar<'a>(app_state: &AppState, config: &Config) -> Paragraph<'a> {
+    let tab_style =
+        |selected: bool| Style::default().fg(if selected { Color::Blue } else { Color::White });
+
+    let mut tabs = Spans::from(
         MenuItems::iterator()
             .enumerate()
             .flat_map(|(index, menu_item)| {
                 let mut items = vec![Span::styled(
                     menu_item.to_string(),
-                    Style::default().fg(if app_state.current_menu == *menu_item {
-                        Color::Blue
-                    } else {
-                        Color::White
-                    }),
+                    tab_style(
+                        app_state.current_menu == *menu_item
+                            && !(*menu_item == MenuItems::Issues
+                                && app_state.current_view.is_some()),
+                    ),
                 )];
 
                 if index != (MenuItems::iterator().count() - 1) {
@@ -150,9 +684,54 @@ fn render_menu_bar<'a>(app_state: &AppState) -> Paragraph<'a> {
                 items
             })
             .collect::<Vec<Span>>(),
-    )];
+    );
+
+    // Saved views are switched to with their number
+    for (index, view) in app_state.views.iter().enumerate().take(9) {
+        tabs.0.push(Span::raw(" | "));
+        tabs.0.push(Span::styled(
+            format!("[{}] {}", index + 1, view.name),
+            tab_style(
+                app_state.current_menu == MenuItems::Issues
+                    && app_state.current_view == Some(index),
+            ),
+        ));
+    }
+
+    tabs.0.push(Span::styled(
+        format!(
+            "  {} ({}@{})",
+            config.profile,
+            config.user_name,
+            config.web_host()
+        ),
+        Style::default().fg(Color::Cyan),
+    ));
+
+    if let Some(search) = &app_state.search_query {
+        tabs.0.push(Span::styled(
+            format!("  search: {}", search.query),
+            Style::default().fg(Color::Yellow),
+        ));
+    } else {
+        tabs.0.push(Span::styled(
+            format!(
+                "  {}",
+                config.repository.as_deref().unwrap_or("all repositories")
+            ),
+            Style::default().fg(Color::DarkGray),
+        ));
+
+        let filters = app_state.active_filters(config).to_string();
+        if !filters.is_empty() {
+            tabs.0.push(Span::styled(
+                format!("  {}", filters),
+                Style::default().fg(Color::Yellow),
+            ));
+        }
+    }
 
-    Paragraph::new(items)
+    Paragraph::new(vec![tabs])
         .alignment(Alignment::Left)
         .block(Block::default().borders(Borders::ALL))
 }